@@ -0,0 +1,31 @@
+use plonk::circuit::{convert_to_json_friendly_circuit, dsl};
+use std::error::Error;
+use std::fs::{create_dir_all, File};
+use std::io::Write;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("Running compile_circuit...");
+
+    // Read the statement written in the expression language
+    let source_path = std::env::args()
+        .nth(1)
+        .ok_or("expected the path of the statement to compile")?;
+    let source = std::fs::read_to_string(&source_path)?;
+    let circuit = dsl::compile(&source)?;
+    println!(
+        "✅ Compiled {} into {} gates",
+        source_path,
+        circuit.number_gates()
+    );
+
+    create_dir_all("data")?;
+    {
+        let json_output = convert_to_json_friendly_circuit(&circuit);
+        let json_str = serde_json::to_string_pretty(&json_output)?;
+        let mut file = File::create("data/circuit.json")?;
+        file.write_all(json_str.as_bytes())?;
+    }
+
+    println!("✅ Circuit written to data/circuit.json");
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use plonk::circuit::dot::convert_to_dot;
+use plonk::circuit::json::CircuitJson;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("Running export_dot...");
+
+    // Open and read circuit.json
+    let file = File::open("data/circuit.json")?;
+    let reader = BufReader::new(file);
+    let circuit_json: CircuitJson = serde_json::from_reader(reader)?;
+    let circuit = circuit_json.into_circuit()?;
+    println!("✅ Loaded circuit from circuit.json");
+
+    std::fs::write("data/circuit.dot", convert_to_dot(&circuit))?;
+    println!("✅ Circuit written to data/circuit.dot, render it with `dot -Tsvg data/circuit.dot`");
+
+    Ok(())
+}
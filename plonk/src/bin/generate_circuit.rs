@@ -0,0 +1,84 @@
+use plonk::circuit::{
+    convert_to_json_friendly_circuit,
+    generators::{self, GeneratedCircuit},
+};
+use plonk::prover::json::ProverInputsJson;
+use plonk::setup_global_params::convert_to_json_friendly_global_params;
+use plonk::setup_proving_key::convert_to_json_friendly_proving_key;
+use plonk::setup_verification_key::convert_to_json_friendly_verification_key;
+use plonk::verifier::json::StatementJson;
+use std::error::Error;
+use std::fs::create_dir_all;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("Running generate_circuit...");
+
+    // Read the kind of circuit and its size, which must be at least the smallest size of that kind
+    let usage =
+        "expected `fibonacci` (size at least 3), `squaring` (at least 1), `exponentiation` \
+        (at least 2) or `dot_product` (at least 1) followed by the size";
+    let kind = std::env::args().nth(1).ok_or(usage)?;
+    let size: u64 = std::env::args().nth(2).ok_or(usage)?.parse()?;
+    let (minimum_size, generate): (u64, fn(u64) -> GeneratedCircuit) = match kind.as_str() {
+        "fibonacci" => (3, |n| generators::fibonacci(n as usize)),
+        "squaring" => (1, |n| generators::repeated_squaring(n as usize)),
+        "exponentiation" => (2, generators::exponentiation),
+        "dot_product" => (1, |n| generators::dot_product(n as usize)),
+        _ => return Err(usage.into()),
+    };
+    if size < minimum_size {
+        return Err(usage.into());
+    }
+    let generated = generate(size);
+    let circuit = &generated.circuit;
+    println!(
+        "✅ Generated {} of size {} with {} gates, d = {}",
+        kind,
+        size,
+        circuit.number_gates(),
+        circuit.d()
+    );
+
+    create_dir_all("data")?;
+    let json_str = serde_json::to_string_pretty(&convert_to_json_friendly_circuit(circuit))?;
+    std::fs::write("data/circuit.json", json_str)?;
+    println!("✅ Circuit written to data/circuit.json");
+
+    // Setup of the generated circuit, so that the keys match it
+    let (setup, proving_key, verification_key) = generated.setup();
+    let json_str = serde_json::to_string_pretty(&convert_to_json_friendly_global_params(&setup))?;
+    std::fs::write("data/srs.json", json_str)?;
+    println!("✅ SRS written to data/srs.json");
+    let json_str =
+        serde_json::to_string_pretty(&convert_to_json_friendly_proving_key(&proving_key))?;
+    std::fs::write("data/proving_key.json", json_str)?;
+    println!("✅ Proving key written to data/proving_key.json");
+    let json_str = serde_json::to_string_pretty(&convert_to_json_friendly_verification_key(
+        &verification_key,
+    ))?;
+    std::fs::write("data/verification_key.json", json_str)?;
+    println!("✅ Verification key written to data/verification_key.json");
+
+    // Inputs of a valid trace, and the statement they prove
+    let to_strings = |values: &[_]| values.iter().map(ToString::to_string).collect();
+    let inputs_json = ProverInputsJson {
+        pub_inputs: to_strings(&generated.pub_inputs),
+        witnesses: to_strings(&generated.witnesses),
+    };
+    std::fs::write(
+        "data/inputs.json",
+        serde_json::to_string_pretty(&inputs_json)?,
+    )?;
+    println!("✅ Prover inputs written to data/inputs.json");
+    let statement_json = StatementJson {
+        pub_inputs: to_strings(&generated.pub_inputs),
+        outputs: to_strings(&generated.outputs()?),
+    };
+    std::fs::write(
+        "data/statement.json",
+        serde_json::to_string_pretty(&statement_json)?,
+    )?;
+    println!("✅ Expected statement written to data/statement.json");
+
+    Ok(())
+}
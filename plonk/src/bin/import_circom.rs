@@ -0,0 +1,48 @@
+use plonk::circuit::circom::{read_wtns, R1cs};
+use plonk::circuit::convert_to_json_friendly_circuit;
+use plonk::prover::json::ProverInputsJson;
+use std::error::Error;
+use std::fs::create_dir_all;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("Running import_circom...");
+
+    // Read the constraints from the .r1cs file
+    let r1cs_path = std::env::args()
+        .nth(1)
+        .ok_or("expected the path of the .r1cs file, optionally followed by a .wtns file")?;
+    let r1cs = R1cs::from_bytes(&std::fs::read(&r1cs_path)?)?;
+    let circuit = r1cs.into_circuit()?;
+    println!(
+        "✅ Converted {} constraints from {} into {} gates",
+        r1cs.constraints.len(),
+        r1cs_path,
+        circuit.number_gates()
+    );
+
+    create_dir_all("data")?;
+    let json_str = serde_json::to_string_pretty(&convert_to_json_friendly_circuit(&circuit))?;
+    std::fs::write("data/circuit.json", json_str)?;
+    println!("✅ Circuit written to data/circuit.json");
+
+    // Read the values of the wires from the .wtns file, if any
+    if let Some(wtns_path) = std::env::args().nth(2) {
+        let wtns = read_wtns(&std::fs::read(&wtns_path)?)?;
+        let inputs = r1cs.prover_inputs(&wtns)?;
+        let to_strings = |values: &[_]| values.iter().map(ToString::to_string).collect();
+        let inputs_json = ProverInputsJson {
+            pub_inputs: to_strings(&inputs.pub_inputs),
+            witnesses: to_strings(&inputs.witnesses),
+        };
+        std::fs::write(
+            "data/inputs.json",
+            serde_json::to_string_pretty(&inputs_json)?,
+        )?;
+        println!(
+            "✅ Prover inputs from {} written to data/inputs.json",
+            wtns_path
+        );
+    }
+
+    Ok(())
+}
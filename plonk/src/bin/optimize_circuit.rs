@@ -0,0 +1,30 @@
+use plonk::circuit::convert_to_json_friendly_circuit;
+use plonk::circuit::json::CircuitJson;
+use plonk::circuit::optimizer::optimize;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("Running optimize_circuit...");
+
+    // Open and read circuit.json
+    let file = File::open("data/circuit.json")?;
+    let reader = BufReader::new(file);
+    let circuit_json: CircuitJson = serde_json::from_reader(reader)?;
+    let circuit = circuit_json.into_circuit()?;
+    println!("✅ Loaded circuit from circuit.json");
+
+    let (optimized, reports) = optimize(&circuit)?;
+    for report in reports {
+        println!("✅ {}", report);
+    }
+    println!("✅ d goes from {} to {}", circuit.d(), optimized.d());
+
+    // The public inputs, witnesses and outputs are unchanged, so are the prover inputs and the statement
+    let json_str = serde_json::to_string_pretty(&convert_to_json_friendly_circuit(&optimized))?;
+    std::fs::write("data/circuit.json", json_str)?;
+    println!("✅ Optimized circuit written to data/circuit.json");
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
+use plonk::circuit::json::CircuitJson;
+use plonk::common::proof::ProofJson;
 use plonk::prover;
+use plonk::prover::json::ProverInputsJson;
 use plonk::setup_global_params::json::SetupGlobalParamsOutputJson;
 use plonk::setup_proving_key::json::SetupProvingKeyOutputJson;
 use plonk::setup_verification_key::json::SetupVerificationKeyOutputJson;
@@ -31,8 +34,42 @@ fn main() -> Result<(), Box<dyn Error>> {
     let verification_key = verification_key_json.into_setup_output();
     println!("✅ Loaded verification key from verification_key.json");
 
-    prover::run(&setup, &proving_key, &verification_key)?;
-    println!("✅ Prover ran successfully");
+    // Open and read circuit.json
+    let file = File::open("data/circuit.json")?;
+    let reader = BufReader::new(file);
+    let circuit_json: CircuitJson = serde_json::from_reader(reader)?;
+    let circuit = circuit_json.into_circuit()?;
+    println!("✅ Loaded circuit from circuit.json");
+
+    // Open and read the prover inputs, data/inputs.json unless another file is given
+    let inputs_path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "data/inputs.json".to_string());
+    let file = File::open(&inputs_path)?;
+    let reader = BufReader::new(file);
+    let inputs_json: ProverInputsJson = serde_json::from_reader(reader)?;
+    let inputs = inputs_json.into_prover_inputs()?;
+    println!("✅ Loaded prover inputs from {}", inputs_path);
+
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &inputs.pub_inputs,
+        &inputs.witnesses,
+    )?;
+    let outputs: Vec<String> = proof.outputs.iter().map(|fr| fr.to_string()).collect();
+    println!(
+        "✅ Prover ran successfully, outputs are [{}]",
+        outputs.join(", ")
+    );
+
+    // Write Proof to a file
+    let proof_json = ProofJson::from(&proof);
+    let json_str = serde_json::to_string_pretty(&proof_json)?;
+    std::fs::write("data/proof.json", json_str)?;
+    println!("✅ Proof written to data/proof.json");
 
     Ok(())
 }
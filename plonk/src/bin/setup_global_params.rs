@@ -1,12 +1,20 @@
+use plonk::circuit::json::CircuitJson;
 use plonk::setup_global_params::{self, convert_to_json_friendly_global_params};
 use std::error::Error;
 use std::fs::{create_dir_all, File};
-use std::io::Write;
+use std::io::{BufReader, Write};
 
 fn main() -> Result<(), Box<dyn Error>> {
     println!("Running setup_global_params...");
 
-    let output = setup_global_params::run();
+    // Open and read circuit.json
+    let file = File::open("data/circuit.json")?;
+    let reader = BufReader::new(file);
+    let circuit_json: CircuitJson = serde_json::from_reader(reader)?;
+    let circuit = circuit_json.into_circuit()?;
+    println!("✅ Loaded circuit from circuit.json");
+
+    let output = setup_global_params::run(&circuit);
 
     create_dir_all("data")?;
     {
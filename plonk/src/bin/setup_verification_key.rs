@@ -1,3 +1,4 @@
+use plonk::circuit::json::CircuitJson;
 use plonk::setup_global_params::json::SetupGlobalParamsOutputJson;
 use plonk::setup_verification_key;
 use plonk::setup_verification_key::convert_to_json_friendly_verification_key;
@@ -15,7 +16,14 @@ fn main() -> Result<(), Box<dyn Error>> {
     let setup = setup_json.into_setup_output();
     println!("✅ Loaded setup parameters from srs.json");
 
-    let output = setup_verification_key::run(&setup);
+    // Open and read circuit.json
+    let file = File::open("data/circuit.json")?;
+    let reader = BufReader::new(file);
+    let circuit_json: CircuitJson = serde_json::from_reader(reader)?;
+    let circuit = circuit_json.into_circuit()?;
+    println!("✅ Loaded circuit from circuit.json");
+
+    let output = setup_verification_key::run(&setup, &circuit);
 
     create_dir_all("data")?;
     {
@@ -2,7 +2,7 @@ use plonk::{
     common::proof::{Proof, ProofJson},
     setup_global_params::json::SetupGlobalParamsOutputJson,
     setup_verification_key::json::SetupVerificationKeyOutputJson,
-    verifier,
+    verifier::{self, json::StatementJson},
 };
 use std::{error::Error, fs::File, io::BufReader};
 
@@ -29,7 +29,35 @@ fn main() -> Result<(), Box<dyn Error>> {
     let proof: Proof = Proof::from(&proof_json);
     println!("✅ Loaded proof from data/proof.json");
 
-    let _ = verifier::run(&setup, &verification_key, &proof);
+    // The expected statement is read from the file given as argument, if any
+    let verified = match std::env::args().nth(1) {
+        Some(statement_path) => {
+            let file = File::open(&statement_path)?;
+            let reader = BufReader::new(file);
+            let statement_json: StatementJson = serde_json::from_reader(reader)?;
+            let statement = statement_json.into_statement()?;
+            println!("✅ Loaded expected statement from {}", statement_path);
+
+            verifier::verify(
+                &setup,
+                &verification_key,
+                &proof,
+                &statement.pub_inputs,
+                &statement.outputs,
+            )
+        }
+        None => {
+            println!(
+                "⚠️ No expected statement given, verifying the statement carried by the proof"
+            );
+            verifier::run(&setup, &verification_key, &proof)
+        }
+    };
+
+    if !verified {
+        return Err("Proof verification failed".into());
+    }
+    println!("✅ Proof verified successfully");
 
     Ok(())
 }
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field};
+
+use crate::{
+    circuit::{Circuit, CircuitError, Gate, Hint, HintOp, Wire},
+    common::protocols::Selectors,
+    setup_proving_key::{compute_proving_key, SetupProvingKeyOutput},
+};
+
+// Builds a circuit gate by gate, without having to deal with its layout over Omega
+//
+// let mut builder = CircuitBuilder::new();
+// let x1 = builder.public_input();
+// let x2 = builder.public_input();
+// let one = builder.constant(Fr::ONE);
+// let a = builder.add(x1, x2);
+// let b = builder.add(x2, one);
+// let out = builder.mul(a, b);
+// builder.output(out);
+// let (circuit, proving_key) = builder.finalize()?;
+#[derive(Clone, Debug, Default)]
+pub struct CircuitBuilder {
+    number_public_inputs: usize,
+    number_witnesses: usize,
+    gates: Vec<Gate>,
+    outputs: Vec<Wire>,
+    hints: Vec<Hint>,
+    // output of the gate fixing each constant, so that every constant is fixed once
+    constants: HashMap<Fr, Wire>,
+}
+
+impl CircuitBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Allocate a new public input
+    pub fn public_input(&mut self) -> Wire {
+        self.number_public_inputs += 1;
+        Wire::PublicInput(self.number_public_inputs - 1)
+    }
+
+    // Allocate a new witness
+    pub fn witness(&mut self) -> Wire {
+        self.number_witnesses += 1;
+        Wire::Witness(self.number_witnesses - 1)
+    }
+
+    // Allocate a new witness computed from input by the witness generator, the prover does not give it.
+    // Nothing constrains its value: the caller must add gates checking it.
+    pub fn hint(&mut self, op: HintOp, input: Wire) -> Wire {
+        assert!(self.is_allocated(input), "{:?} is not allocated", input);
+        self.hints.push(Hint {
+            witness: self.number_witnesses,
+            op,
+            input,
+        });
+        self.witness()
+    }
+
+    // Return a wire fixed to k by a constant gate, the gate is shared by all uses of k
+    pub fn constant(&mut self, k: Fr) -> Wire {
+        if let Some(&wire) = self.constants.get(&k) {
+            return wire;
+        }
+        let wire = self.push_gate(Gate::constant(k));
+        self.constants.insert(k, wire);
+        wire
+    }
+
+    // Append an addition gate and return its output a + b
+    pub fn add(&mut self, a: Wire, b: Wire) -> Wire {
+        self.push_gate(Gate::addition(a, b))
+    }
+
+    // Append a subtraction gate and return its output a - b
+    pub fn sub(&mut self, a: Wire, b: Wire) -> Wire {
+        self.push_gate(Gate::subtraction(a, b))
+    }
+
+    // Append a multiplication gate and return its output a * b
+    pub fn mul(&mut self, a: Wire, b: Wire) -> Wire {
+        self.push_gate(Gate::multiplication(a, b))
+    }
+
+    // Append a gate returning k * a
+    pub fn mul_constant(&mut self, a: Wire, k: Fr) -> Wire {
+        self.gate(
+            Selectors {
+                qL: k,
+                qO: -Fr::ONE,
+                ..Default::default()
+            },
+            Some(a),
+            None,
+        )
+    }
+
+    // Append a gate returning a + k
+    pub fn add_constant(&mut self, a: Wire, k: Fr) -> Wire {
+        self.gate(
+            Selectors {
+                qL: Fr::ONE,
+                qO: -Fr::ONE,
+                qC: k,
+                ..Default::default()
+            },
+            Some(a),
+            None,
+        )
+    }
+
+    // Append a gate returning k_a * a + k_b * b + k
+    pub fn linear_combination(&mut self, a: Wire, k_a: Fr, b: Wire, k_b: Fr, k: Fr) -> Wire {
+        self.push_gate(Gate::linear_combination(a, k_a, b, k_b, k))
+    }
+
+    // Append a gate with arbitrary selectors and return its output.
+    // The output is constrained by qL*a + qR*b + qO*c + qM*a*b + qC = 0, unused inputs are set to zero
+    // so the selectors reading them must be zero. Panics otherwise, see try_gate.
+    // With qO = 0 nothing constrains the output, the circuit is rejected by build if it is read: use constrain.
+    pub fn gate(
+        &mut self,
+        selectors: Selectors<Fr>,
+        left: Option<Wire>,
+        right: Option<Wire>,
+    ) -> Wire {
+        self.try_gate(selectors, left, right)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    // Append a gate with arbitrary selectors and return its output, or an error if a selector reads an
+    // unused input
+    pub fn try_gate(
+        &mut self,
+        selectors: Selectors<Fr>,
+        left: Option<Wire>,
+        right: Option<Wire>,
+    ) -> Result<Wire, CircuitError> {
+        let gate = Gate {
+            selectors,
+            left,
+            right,
+        };
+        if gate.reads_unwired_input() {
+            return Err(CircuitError::UnwiredInputWithNonZeroSelector {
+                gate: format!("gate#{}", self.gates.len()),
+            });
+        }
+        Ok(self.push_gate(gate))
+    }
+
+    // Append a gate enforcing qL*a + qR*b + qM*a*b + qC = 0 on its inputs, whose selector qO must be zero.
+    // Its output is not constrained, so it is not returned.
+    pub fn constrain(&mut self, selectors: Selectors<Fr>, left: Option<Wire>, right: Option<Wire>) {
+        assert!(
+            selectors.qO == Fr::ZERO,
+            "A constraint has qO = 0, use gate for a gate with an output"
+        );
+        self.gate(selectors, left, right);
+    }
+
+    // Return 1/x through a hint checked by x * inv = 1, which also enforces x != 0
+    pub fn inverse(&mut self, x: Wire) -> Wire {
+        let inverse = self.hint(HintOp::Inverse, x);
+        self.constrain(
+            Selectors {
+                qM: Fr::ONE,
+                qC: -Fr::ONE,
+                ..Default::default()
+            },
+            Some(x),
+            Some(inverse),
+        );
+        inverse
+    }
+
+    // Return a / b, for b != 0
+    pub fn div(&mut self, a: Wire, b: Wire) -> Wire {
+        let inverse = self.inverse(b);
+        self.mul(a, inverse)
+    }
+
+    // Return a square root of x through a hint checked by r * r = x, which also enforces that x is a square
+    pub fn sqrt(&mut self, x: Wire) -> Wire {
+        let root = self.hint(HintOp::SquareRoot, x);
+        let square = self.mul(root, root);
+        self.enforce_equal(square, x);
+        root
+    }
+
+    // Append a gate enforcing a = b
+    pub fn enforce_equal(&mut self, a: Wire, b: Wire) {
+        self.constrain(
+            Selectors {
+                qL: Fr::ONE,
+                qR: -Fr::ONE,
+                ..Default::default()
+            },
+            Some(a),
+            Some(b),
+        );
+    }
+
+    // Declare the value of a wire as a public output of the circuit
+    pub fn output(&mut self, wire: Wire) {
+        assert!(self.is_allocated(wire), "{:?} is not allocated", wire);
+        assert!(
+            !self.outputs.contains(&wire),
+            "{:?} is already an output",
+            wire
+        );
+        self.outputs.push(wire);
+    }
+
+    fn push_gate(&mut self, gate: Gate) -> Wire {
+        for wire in gate.left.iter().chain(gate.right.iter()) {
+            assert!(self.is_allocated(*wire), "{:?} is not allocated", wire);
+        }
+
+        self.gates.push(gate);
+        Wire::Gate(self.gates.len() - 1)
+    }
+
+    fn is_allocated(&self, wire: Wire) -> bool {
+        match wire {
+            Wire::PublicInput(j) => j < self.number_public_inputs,
+            Wire::Witness(k) => k < self.number_witnesses,
+            Wire::Gate(l) => l < self.gates.len(),
+        }
+    }
+
+    // Build the circuit, or return an error if it reads the output of a gate with qO = 0
+    pub fn build(self) -> Result<Circuit, CircuitError> {
+        let circuit = Circuit {
+            number_public_inputs: self.number_public_inputs,
+            number_witnesses: self.number_witnesses,
+            gates: self.gates,
+            outputs: self.outputs,
+            hints: self.hints,
+        };
+        if let Some(l) = circuit.unconstrained_output_read() {
+            return Err(CircuitError::UnconstrainedOutputRead {
+                gate: format!("gate#{}", l),
+            });
+        }
+        Ok(circuit)
+    }
+
+    // Build the circuit and compute its selector polynomials Q and wiring polynomial W
+    pub fn finalize(self) -> Result<(Circuit, SetupProvingKeyOutput), CircuitError> {
+        let circuit = self.build()?;
+        let proving_key = compute_proving_key(&circuit);
+
+        Ok((circuit, proving_key))
+    }
+}
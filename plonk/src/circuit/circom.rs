@@ -0,0 +1,289 @@
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, BigInteger, Field, PrimeField};
+
+use crate::{
+    circuit::{builder::CircuitBuilder, Circuit, CircuitError, Wire},
+    common::protocols::Selectors,
+    prover::json::ProverInputs,
+};
+
+// Linear combination sum(coefficient * wire#i) of circom wires, wire#0 being the constant 1
+pub type LinearCombination = Vec<(usize, Fr)>;
+
+// R1CS constraint a * b = c
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1csConstraint {
+    pub a: LinearCombination,
+    pub b: LinearCombination,
+    pub c: LinearCombination,
+}
+
+// Constraint system read from a circom .r1cs file (circom --r1cs --prime bls12381), or synthesized by arkworks.
+// Wires are ordered as follows: the constant 1, the public outputs, the public inputs, the private inputs
+// and the internal signals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1cs {
+    pub number_wires: usize,
+    pub number_public_outputs: usize,
+    pub number_public_inputs: usize,
+    pub number_private_inputs: usize,
+    pub constraints: Vec<R1csConstraint>,
+}
+
+impl R1cs {
+    // Parse the binary .r1cs format: header section (type 1) and constraints section (type 2)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CircuitError> {
+        let sections = read_sections(bytes, b"r1cs")?;
+
+        let mut header = Reader::new(find_section(&sections, 1)?);
+        read_prime(&mut header)?;
+        let number_wires = header.u32()? as usize;
+        let number_public_outputs = header.u32()? as usize;
+        let number_public_inputs = header.u32()? as usize;
+        let number_private_inputs = header.u32()? as usize;
+        let _number_labels = header.u64()?;
+        let number_constraints = header.u32()? as usize;
+        if 1 + number_public_outputs + number_public_inputs + number_private_inputs > number_wires {
+            return Err(invalid("more inputs and outputs than wires"));
+        }
+
+        let mut reader = Reader::new(find_section(&sections, 2)?);
+        let mut read_linear_combination = || -> Result<LinearCombination, CircuitError> {
+            let number_terms = reader.u32()?;
+            (0..number_terms)
+                .map(|_| {
+                    let wire = reader.u32()? as usize;
+                    if wire >= number_wires {
+                        return Err(invalid(&format!("unknown wire#{}", wire)));
+                    }
+                    Ok((wire, reader.field_element()?))
+                })
+                .collect()
+        };
+        let constraints = (0..number_constraints)
+            .map(|_| {
+                Ok(R1csConstraint {
+                    a: read_linear_combination()?,
+                    b: read_linear_combination()?,
+                    c: read_linear_combination()?,
+                })
+            })
+            .collect::<Result<Vec<_>, CircuitError>>()?;
+
+        Ok(R1cs {
+            number_wires,
+            number_public_outputs,
+            number_public_inputs,
+            number_private_inputs,
+            constraints,
+        })
+    }
+
+    fn is_public_input(&self, i: usize) -> bool {
+        i > self.number_public_outputs
+            && i <= self.number_public_outputs + self.number_public_inputs
+    }
+
+    // Circuit enforcing every constraint a * b = c. The public inputs of circom are the public inputs of the
+    // circuit, its public outputs are outputs of the circuit and all other signals are witnesses.
+    // Each constraint takes two gates: t = a * b - k_c computed from the affine forms of a and b, then
+    // t - c = 0 checked by a gate with qO = 0. Linear combinations of more than one signal are first
+    // reduced to a single wire by a chain of addition gates.
+    pub fn into_circuit(&self) -> Result<Circuit, CircuitError> {
+        let mut builder = CircuitBuilder::new();
+        let wires: Vec<Option<Wire>> = (0..self.number_wires)
+            .map(|i| match i {
+                0 => None,
+                i if self.is_public_input(i) => Some(builder.public_input()),
+                _ => Some(builder.witness()),
+            })
+            .collect();
+
+        for constraint in &self.constraints {
+            let (a, k_a) = reduce(&mut builder, &wires, &constraint.a);
+            let (b, k_b) = reduce(&mut builder, &wires, &constraint.b);
+            let (c, k_c) = reduce(&mut builder, &wires, &constraint.c);
+
+            // (s_a*a + k_a) * (s_b*b + k_b) - k_c
+            let (a, s_a) = a.map_or((None, Fr::ZERO), |(a, s_a)| (Some(a), s_a));
+            let (b, s_b) = b.map_or((None, Fr::ZERO), |(b, s_b)| (Some(b), s_b));
+            let mut selectors = Selectors {
+                qL: s_a * k_b,
+                qR: k_a * s_b,
+                qO: -Fr::ONE,
+                qM: s_a * s_b,
+                qC: k_a * k_b - k_c,
+            };
+            match c {
+                // a * b - k_c = s_c * c
+                Some((c, s_c)) => {
+                    let t = builder.gate(selectors, a, b);
+                    builder.constrain(
+                        Selectors {
+                            qL: Fr::ONE,
+                            qR: -s_c,
+                            ..Default::default()
+                        },
+                        Some(t),
+                        Some(c),
+                    );
+                }
+                // a * b - k_c = 0
+                None => {
+                    selectors.qO = Fr::ZERO;
+                    builder.constrain(selectors, a, b);
+                }
+            }
+        }
+
+        for wire in &wires[1..=self.number_public_outputs] {
+            builder.output(wire.unwrap());
+        }
+        builder.build()
+    }
+
+    // Split the values of the wires, read from a .wtns file, into the inputs of the prover
+    pub fn prover_inputs(&self, wtns: &[Fr]) -> Result<ProverInputs, CircuitError> {
+        if wtns.len() != self.number_wires {
+            return Err(CircuitError::WrongNumberOfValues {
+                kind: "circom wire",
+                expected: self.number_wires,
+                got: wtns.len(),
+            });
+        }
+        if wtns[0] != Fr::ONE {
+            return Err(invalid("wire#0 must be the constant 1"));
+        }
+
+        let (pub_inputs, witnesses) =
+            (1..self.number_wires).partition(|&i| self.is_public_input(i));
+        let values = |indices: Vec<usize>| indices.into_iter().map(|i| wtns[i]).collect();
+        Ok(ProverInputs {
+            pub_inputs: values(pub_inputs),
+            witnesses: values(witnesses),
+        })
+    }
+}
+
+// Parse the binary .wtns format: header section (type 1) and values of the wires (type 2)
+pub fn read_wtns(bytes: &[u8]) -> Result<Vec<Fr>, CircuitError> {
+    let sections = read_sections(bytes, b"wtns")?;
+
+    let mut header = Reader::new(find_section(&sections, 1)?);
+    read_prime(&mut header)?;
+    let number_wires = header.u32()?;
+
+    let mut reader = Reader::new(find_section(&sections, 2)?);
+    (0..number_wires).map(|_| reader.field_element()).collect()
+}
+
+// Reduce a linear combination to s*w + k, adding the gates summing its wires when there are more than one
+fn reduce(
+    builder: &mut CircuitBuilder,
+    wires: &[Option<Wire>],
+    linear_combination: &LinearCombination,
+) -> (Option<(Wire, Fr)>, Fr) {
+    let mut k = Fr::ZERO;
+    let mut sum: Option<(Wire, Fr)> = None;
+    for &(i, coefficient) in linear_combination {
+        sum = match (wires[i], sum) {
+            (None, sum) => {
+                k += coefficient;
+                sum
+            }
+            (Some(wire), None) => Some((wire, coefficient)),
+            (Some(wire), Some((acc, s))) => Some((
+                builder.linear_combination(acc, s, wire, coefficient, Fr::ZERO),
+                Fr::ONE,
+            )),
+        };
+    }
+    (sum, k)
+}
+
+fn invalid(message: &str) -> CircuitError {
+    CircuitError::InvalidCircomFile(message.to_string())
+}
+
+// Split a circom binary file into its sections (type, content) after checking the magic and the version
+fn read_sections<'a>(
+    bytes: &'a [u8],
+    magic: &[u8; 4],
+) -> Result<Vec<(u32, &'a [u8])>, CircuitError> {
+    let mut reader = Reader::new(bytes);
+    if reader.bytes(4)? != magic {
+        return Err(invalid(&format!(
+            "expected magic `{}`",
+            String::from_utf8_lossy(magic)
+        )));
+    }
+    let _version = reader.u32()?;
+    let number_sections = reader.u32()?;
+    (0..number_sections)
+        .map(|_| {
+            let section_type = reader.u32()?;
+            let size = reader.u64()? as usize;
+            Ok((section_type, reader.bytes(size)?))
+        })
+        .collect()
+}
+
+fn find_section<'a>(
+    sections: &[(u32, &'a [u8])],
+    section_type: u32,
+) -> Result<&'a [u8], CircuitError> {
+    sections
+        .iter()
+        .find(|(t, _)| *t == section_type)
+        .map(|(_, content)| *content)
+        .ok_or_else(|| invalid(&format!("missing section of type {}", section_type)))
+}
+
+// Field elements must be 32 bytes long and the prime must be the order of Fr (circom --prime bls12381)
+fn read_prime(reader: &mut Reader) -> Result<(), CircuitError> {
+    let field_size = reader.u32()?;
+    if field_size != 32 || reader.bytes(32)? != Fr::MODULUS.to_bytes_le() {
+        return Err(invalid(
+            "the prime is not the order of the BLS12-381 scalar field",
+        ));
+    }
+    Ok(())
+}
+
+// Little-endian reader over the content of a circom binary file
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8], CircuitError> {
+        if self.bytes.len() < n {
+            return Err(invalid("unexpected end of file"));
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, CircuitError> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, CircuitError> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    // Field element in canonical form, less than the prime
+    fn field_element(&mut self) -> Result<Fr, CircuitError> {
+        let bytes = self.bytes(32)?;
+        let value = Fr::from_le_bytes_mod_order(bytes);
+        if value.into_bigint().to_bytes_le() != bytes {
+            return Err(invalid("field element is not reduced modulo the prime"));
+        }
+        Ok(value)
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::Write;
+
+use crate::circuit::Circuit;
+
+// Graphviz DOT description of the circuit laid out over Omega of size d.
+// Public inputs and witnesses are boxes labelled with their slot w^-(j+1), gates are labelled with their
+// operation (or their selectors) and their slots w^3l..w^3l+2, and outputs have a double border.
+// Each edge links the slot defining a value to a gate input reading it, as connected by a cycle of W.
+pub fn convert_to_dot(circuit: &Circuit) -> String {
+    let d = circuit.d();
+    let number_gate_slots = 3 * circuit.number_gates();
+    let outputs = circuit.output_indices(d);
+
+    // Node of the value defined at slot i: a gate output, a public input or a witness
+    let node = |i: usize| {
+        if i < number_gate_slots {
+            format!("g{}", i / 3)
+        } else if d - 1 - i < circuit.number_public_inputs {
+            format!("x{}", d - 1 - i)
+        } else {
+            format!("w{}", d - 1 - circuit.number_public_inputs - i)
+        }
+    };
+    let peripheries = |i: usize| if outputs.contains(&i) { 2 } else { 1 };
+
+    let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+    let inputs = (0..circuit.number_public_inputs + circuit.number_witnesses).map(|j| d - 1 - j);
+    for i in inputs {
+        writeln!(
+            dot,
+            "    {} [shape=box, peripheries={}, label=\"{}\\nw^-{}\"];",
+            node(i),
+            peripheries(i),
+            node(i),
+            d - i
+        )
+        .unwrap();
+    }
+    for (l, gate) in circuit.gates.iter().enumerate() {
+        let s = &gate.selectors;
+        let op = match gate.op() {
+            "const" => format!("const {}", s.qC),
+            "gate" => format!(
+                "qL={} qR={} qO={} qM={} qC={}",
+                s.qL, s.qR, s.qO, s.qM, s.qC
+            ),
+            op => op.to_string(),
+        };
+        writeln!(
+            dot,
+            "    g{} [peripheries={}, label=\"g{}: {}\\nw^{}..w^{}\"];",
+            l,
+            peripheries(3 * l + 2),
+            l,
+            op,
+            3 * l,
+            3 * l + 2
+        )
+        .unwrap();
+    }
+
+    // Every cycle gathers the slot defining a value, i.e. not a gate input, and the gate inputs reading it
+    for cycle in circuit.wiring_cycles(d) {
+        let is_gate_input = |&i: &usize| i < number_gate_slots && i % 3 != 2;
+        let Some(&source) = cycle.iter().find(|i| !is_gate_input(i)) else {
+            continue;
+        };
+        for &i in cycle.iter().filter(|i| is_gate_input(i)) {
+            let port = if i % 3 == 0 { "a" } else { "b" };
+            writeln!(
+                dot,
+                "    {} -> g{} [label=\"{}\"];",
+                node(source),
+                i / 3,
+                port
+            )
+            .unwrap();
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
@@ -0,0 +1,372 @@
+use std::collections::{HashMap, HashSet};
+
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field, Zero};
+
+use crate::{
+    circuit::{builder::CircuitBuilder, Circuit, CircuitError, Wire},
+    common::{protocols::Selectors, utils::parse_field_element},
+};
+
+// Compile a statement written with arithmetic expressions into a circuit
+//
+// public x1, x2
+// witness w
+// a = (x1 + x2) * (x2 + 1)
+// output a, a * w - 3
+//
+// Statements are separated by newlines or `;` and `#` starts a comment.
+// - `public` and `witness` declare the public inputs and the witnesses, in order
+// - `name = expression` names the value of an expression made of +, -, *, /, parentheses, variables and constants
+// - `output` lists the public outputs, without it the last named value is the output
+// Operations on constants are folded into the selectors of a single gate. Dividing by a variable enforces
+// that it is not zero, its inverse is computed by a hint.
+pub fn compile(source: &str) -> Result<Circuit, CircuitError> {
+    let tokens = tokenize(source)?;
+    let mut compiler = Compiler {
+        tokens: &tokens,
+        position: 0,
+        builder: CircuitBuilder::new(),
+        variables: HashMap::new(),
+        outputs: vec![],
+        has_output_statement: false,
+        last_named: None,
+    };
+    compiler.compile_program()?;
+
+    let mut builder = compiler.builder;
+    let outputs = match (compiler.has_output_statement, compiler.last_named) {
+        (false, Some(last_named)) => vec![last_named],
+        _ => compiler.outputs,
+    };
+    // names aliasing the same wire are distinct outputs, each further one reads a copy of the wire
+    let mut declared = HashSet::new();
+    for output in outputs {
+        let mut wire = match output {
+            Value::Constant(k) => builder.constant(k),
+            Value::Wire(wire) => wire,
+        };
+        if !declared.insert(wire) {
+            wire = builder.mul_constant(wire, Fr::ONE);
+            declared.insert(wire);
+        }
+        builder.output(wire);
+    }
+
+    builder.build()
+}
+
+const KEYWORDS: [&str; 3] = ["public", "witness", "output"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    Number(String),
+    Symbol(char),
+    Separator,
+}
+
+// Split the source into tokens, each one with its line number
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, CircuitError> {
+    let mut tokens = vec![];
+    let mut chars = source.chars().peekable();
+    let mut line = 1;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                tokens.push((Token::Separator, line));
+                line += 1;
+            }
+            ';' => tokens.push((Token::Separator, line)),
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '+' | '-' | '*' | '/' | '(' | ')' | '=' | ',' => tokens.push((Token::Symbol(c), line)),
+            c if c.is_whitespace() => {}
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|&c| c.is_ascii_alphanumeric() || c == '_') {
+                    word.push(c);
+                }
+                let token = if c.is_ascii_digit() {
+                    Token::Number(word)
+                } else {
+                    Token::Identifier(word)
+                };
+                tokens.push((token, line));
+            }
+            c => {
+                return Err(CircuitError::Syntax {
+                    line,
+                    message: format!("unexpected character `{}`", c),
+                })
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Value of an expression: a constant known at compile time or a wire of the circuit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Value {
+    Constant(Fr),
+    Wire(Wire),
+}
+
+// Recursive descent parser appending the gates of every expression to the builder
+struct Compiler<'a> {
+    tokens: &'a [(Token, usize)],
+    position: usize,
+    builder: CircuitBuilder,
+    variables: HashMap<String, Value>,
+    outputs: Vec<Value>,
+    has_output_statement: bool,
+    last_named: Option<Value>,
+}
+
+impl Compiler<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    // Line of the next token, or of the last one at the end of the source
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn syntax_error(&self, message: String) -> CircuitError {
+        CircuitError::Syntax {
+            line: self.line(),
+            message,
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), CircuitError> {
+        match self.peek() {
+            Some(Token::Symbol(c)) if *c == symbol => {
+                self.next();
+                Ok(())
+            }
+            token => Err(self.syntax_error(format!("expected `{}`, found {:?}", symbol, token))),
+        }
+    }
+
+    fn expect_name(&mut self) -> Result<String, CircuitError> {
+        match self.peek() {
+            Some(Token::Identifier(name)) if !KEYWORDS.contains(&name.as_str()) => {
+                let name = name.clone();
+                self.next();
+                Ok(name)
+            }
+            token => Err(self.syntax_error(format!("expected a variable name, found {:?}", token))),
+        }
+    }
+
+    fn declare(&mut self, name: String, value: Value) -> Result<(), CircuitError> {
+        if self.variables.contains_key(&name) {
+            return Err(CircuitError::DuplicateVariable(name));
+        }
+        self.variables.insert(name, value);
+        Ok(())
+    }
+
+    // program := statement (separator statement)*
+    fn compile_program(&mut self) -> Result<(), CircuitError> {
+        loop {
+            while self.peek() == Some(&Token::Separator) {
+                self.next();
+            }
+            if self.peek().is_none() {
+                return Ok(());
+            }
+            self.compile_statement()?;
+            match self.peek() {
+                None | Some(Token::Separator) => {}
+                token => {
+                    return Err(
+                        self.syntax_error(format!("expected end of statement, found {:?}", token))
+                    )
+                }
+            }
+        }
+    }
+
+    // statement := `public` names | `witness` names | `output` expressions | name `=` expression
+    fn compile_statement(&mut self) -> Result<(), CircuitError> {
+        let keyword = match self.peek() {
+            Some(Token::Identifier(word)) if KEYWORDS.contains(&word.as_str()) => word.clone(),
+            _ => {
+                let name = self.expect_name()?;
+                self.expect_symbol('=')?;
+                let value = self.compile_expression()?;
+                self.declare(name, value)?;
+                self.last_named = Some(value);
+                return Ok(());
+            }
+        };
+        self.next();
+
+        loop {
+            match keyword.as_str() {
+                "public" => {
+                    let name = self.expect_name()?;
+                    let wire = self.builder.public_input();
+                    self.declare(name, Value::Wire(wire))?;
+                }
+                "witness" => {
+                    let name = self.expect_name()?;
+                    let wire = self.builder.witness();
+                    self.declare(name, Value::Wire(wire))?;
+                }
+                _ => {
+                    let value = self.compile_expression()?;
+                    self.outputs.push(value);
+                    self.has_output_statement = true;
+                }
+            }
+            if self.peek() != Some(&Token::Symbol(',')) {
+                return Ok(());
+            }
+            self.next();
+        }
+    }
+
+    // expression := term ((`+` | `-`) term)*
+    fn compile_expression(&mut self) -> Result<Value, CircuitError> {
+        let mut value = self.compile_term()?;
+        loop {
+            match self.peek() {
+                Some(Token::Symbol('+')) => {
+                    self.next();
+                    let term = self.compile_term()?;
+                    value = self.add(value, term);
+                }
+                Some(Token::Symbol('-')) => {
+                    self.next();
+                    let term = self.compile_term()?;
+                    value = self.sub(value, term);
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    // term := factor ((`*` | `/`) factor)*
+    fn compile_term(&mut self) -> Result<Value, CircuitError> {
+        let mut value = self.compile_factor()?;
+        loop {
+            match self.peek() {
+                Some(Token::Symbol('*')) => {
+                    self.next();
+                    let factor = self.compile_factor()?;
+                    value = self.mul(value, factor);
+                }
+                Some(Token::Symbol('/')) => {
+                    self.next();
+                    let factor = self.compile_factor()?;
+                    value = self.div(value, factor)?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    // factor := `-` factor | number | name | `(` expression `)`
+    fn compile_factor(&mut self) -> Result<Value, CircuitError> {
+        match self.next() {
+            Some(Token::Symbol('-')) => {
+                let factor = self.compile_factor()?;
+                Ok(self.mul(factor, Value::Constant(-Fr::ONE)))
+            }
+            Some(Token::Number(number)) => parse_field_element(&number)
+                .map(Value::Constant)
+                .ok_or(CircuitError::InvalidFieldElement(number)),
+            Some(Token::Identifier(name)) if !KEYWORDS.contains(&name.as_str()) => self
+                .variables
+                .get(&name)
+                .copied()
+                .ok_or(CircuitError::UnknownVariable(name)),
+            Some(Token::Symbol('(')) => {
+                let value = self.compile_expression()?;
+                self.expect_symbol(')')?;
+                Ok(value)
+            }
+            token => {
+                self.position -= 1;
+                Err(self.syntax_error(format!("expected an expression, found {:?}", token)))
+            }
+        }
+    }
+
+    fn add(&mut self, a: Value, b: Value) -> Value {
+        match (a, b) {
+            (Value::Constant(a), Value::Constant(b)) => Value::Constant(a + b),
+            (Value::Wire(a), Value::Constant(k)) | (Value::Constant(k), Value::Wire(a)) => {
+                if k.is_zero() {
+                    Value::Wire(a)
+                } else {
+                    Value::Wire(self.builder.add_constant(a, k))
+                }
+            }
+            (Value::Wire(a), Value::Wire(b)) => Value::Wire(self.builder.add(a, b)),
+        }
+    }
+
+    fn sub(&mut self, a: Value, b: Value) -> Value {
+        match (a, b) {
+            (Value::Constant(a), Value::Constant(b)) => Value::Constant(a - b),
+            (a, Value::Constant(k)) => self.add(a, Value::Constant(-k)),
+            // k - b = -b + k
+            (Value::Constant(k), Value::Wire(b)) => Value::Wire(self.builder.gate(
+                Selectors {
+                    qL: -Fr::ONE,
+                    qO: -Fr::ONE,
+                    qC: k,
+                    ..Default::default()
+                },
+                Some(b),
+                None,
+            )),
+            (Value::Wire(a), Value::Wire(b)) => Value::Wire(self.builder.sub(a, b)),
+        }
+    }
+
+    fn mul(&mut self, a: Value, b: Value) -> Value {
+        match (a, b) {
+            (Value::Constant(a), Value::Constant(b)) => Value::Constant(a * b),
+            (Value::Wire(a), Value::Constant(k)) | (Value::Constant(k), Value::Wire(a)) => {
+                if k.is_zero() {
+                    Value::Constant(Fr::ZERO)
+                } else if k == Fr::ONE {
+                    Value::Wire(a)
+                } else {
+                    Value::Wire(self.builder.mul_constant(a, k))
+                }
+            }
+            (Value::Wire(a), Value::Wire(b)) => Value::Wire(self.builder.mul(a, b)),
+        }
+    }
+
+    // The inverse of a wire is a hint checked by a gate, which also enforces that the wire is not zero
+    fn div(&mut self, a: Value, b: Value) -> Result<Value, CircuitError> {
+        match b {
+            Value::Constant(k) => match k.inverse() {
+                Some(inverse) => Ok(self.mul(a, Value::Constant(inverse))),
+                None => Err(self.syntax_error("division by zero".to_string())),
+            },
+            Value::Wire(b) => {
+                let inverse = self.builder.inverse(b);
+                Ok(self.mul(a, Value::Wire(inverse)))
+            }
+        }
+    }
+}
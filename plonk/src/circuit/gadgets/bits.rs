@@ -0,0 +1,65 @@
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, BigInteger, Field, PrimeField};
+
+use crate::{
+    circuit::{builder::CircuitBuilder, HintOp, Wire},
+    common::protocols::Selectors,
+};
+
+impl CircuitBuilder {
+    // Append a gate enforcing x * (x - 1) = 0, i.e. x is 0 or 1
+    pub fn enforce_boolean(&mut self, x: Wire) {
+        self.constrain(
+            Selectors {
+                qL: -Fr::ONE,
+                qM: Fr::ONE,
+                ..Default::default()
+            },
+            Some(x),
+            Some(x),
+        );
+    }
+
+    // Allocate n witnesses b_0..b_n-1 with hints, enforced to be bits such that x = sum(b_i * 2^i).
+    // n must be less than the bit size of Fr so that the decomposition is unique.
+    pub fn decompose(&mut self, x: Wire, n: usize) -> Vec<Wire> {
+        assert!(
+            n < Fr::MODULUS_BIT_SIZE as usize,
+            "{} bits do not fit in a field element",
+            n
+        );
+
+        let bits: Vec<Wire> = (0..n).map(|i| self.hint(HintOp::Bit(i), x)).collect();
+        for &bit in &bits {
+            self.enforce_boolean(bit);
+        }
+        let recomposed = self.recompose(&bits);
+        self.enforce_equal(recomposed, x);
+        bits
+    }
+
+    // Return sum(b_i * 2^i), from the least significant bit b_0
+    pub fn recompose(&mut self, bits: &[Wire]) -> Wire {
+        match bits {
+            [] => self.constant(Fr::ZERO),
+            [bit] => *bit,
+            [b0, b1, rest @ ..] => {
+                let mut power = Fr::from(2);
+                let mut sum = self.linear_combination(*b0, Fr::ONE, *b1, power, Fr::ZERO);
+                for &bit in rest {
+                    power.double_in_place();
+                    sum = self.linear_combination(sum, Fr::ONE, bit, power, Fr::ZERO);
+                }
+                sum
+            }
+        }
+    }
+}
+
+// The n least significant bits of x, from the least significant one
+pub fn bits_le(x: Fr, n: usize) -> Vec<Fr> {
+    let bits = x.into_bigint().to_bits_le();
+    (0..n)
+        .map(|i| Fr::from(bits.get(i).copied().unwrap_or(false)))
+        .collect()
+}
@@ -0,0 +1,29 @@
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+
+use crate::circuit::{builder::CircuitBuilder, Wire};
+
+impl CircuitBuilder {
+    // Enforce 0 <= x < 2^n, allocating the n bits of x as witnesses with hints
+    pub fn range_check(&mut self, x: Wire, n: usize) {
+        self.decompose(x, n);
+    }
+
+    // Return 1 if a < b and 0 otherwise, for a and b known to be less than 2^n (e.g. range checked).
+    // a - b + 2^n lies in [0, 2^(n+1)) and its bit n is set if and only if a >= b.
+    pub fn less_than(&mut self, a: Wire, b: Wire, n: usize) -> Wire {
+        let difference = self.linear_combination(a, Fr::ONE, b, -Fr::ONE, power_of_two(n));
+        let bits = self.decompose(difference, n + 1);
+        self.not(bits[n])
+    }
+
+    // Return 1 if a <= b and 0 otherwise, i.e. 1 - (b < a), for a and b less than 2^n
+    pub fn less_or_equal(&mut self, a: Wire, b: Wire, n: usize) -> Wire {
+        let greater_than = self.less_than(b, a, n);
+        self.not(greater_than)
+    }
+}
+
+fn power_of_two(n: usize) -> Fr {
+    Fr::from(2).pow([n as u64])
+}
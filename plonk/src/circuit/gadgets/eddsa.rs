@@ -0,0 +1,134 @@
+use ark_bls12_381::Fr;
+use ark_ff::{BigInteger, Field, PrimeField};
+use blake2::{Blake2b512, Digest};
+use num_bigint::BigUint;
+
+use crate::{
+    circuit::{
+        builder::CircuitBuilder,
+        gadgets::{
+            jubjub::{self, Point, PointWires},
+            poseidon,
+        },
+        HintOp, Wire,
+    },
+    common::protocols::Selectors,
+};
+
+// EdDSA over Jubjub with the Poseidon hash, cheap to verify in a circuit: a signature (R, s) of message m
+// under the public key A = sk * G is valid if 8*s*G = 8*R + 8*c*A with the challenge c = H(R, A, m).
+// Multiplying by the cofactor 8 makes the verification ignore small order components of R and A.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r: Point,
+    // less than the order of the subgroup
+    pub s: BigUint,
+}
+
+pub fn public_key(secret_key: &BigUint) -> Point {
+    Point::GENERATOR.scalar_mul(secret_key)
+}
+
+pub fn challenge(r: Point, public_key: Point, message: Fr) -> Fr {
+    poseidon::hash(&[r.x, r.y, public_key.x, public_key.y, message])
+}
+
+// Sign with a nonce derived from the secret key and the message, so that it is never reused for
+// another message
+pub fn sign(secret_key: &BigUint, message: Fr) -> Signature {
+    let order = jubjub::order();
+    let nonce_hash = Blake2b512::new()
+        .chain_update(secret_key.to_bytes_le())
+        .chain_update(message.into_bigint().to_bytes_le())
+        .finalize();
+    let nonce = BigUint::from_bytes_le(&nonce_hash) % &order;
+
+    let r = Point::GENERATOR.scalar_mul(&nonce);
+    let c: BigUint = challenge(r, public_key(secret_key), message).into();
+    let s = (nonce + c * secret_key) % &order;
+    Signature { r, s }
+}
+
+pub fn verify(public_key: Point, message: Fr, signature: &Signature) -> bool {
+    let Signature { r, s } = signature;
+    if !public_key.is_on_curve() || !r.is_on_curve() || *s >= jubjub::order() {
+        return false;
+    }
+
+    let c: BigUint = challenge(*r, public_key, message).into();
+    let cofactor = BigUint::from(8u8);
+    Point::GENERATOR.scalar_mul(&(s * &cofactor))
+        == (*r + public_key.scalar_mul(&c)).scalar_mul(&cofactor)
+}
+
+impl CircuitBuilder {
+    // Enforce that (r, s) is a valid signature of message under public_key, as verify does. The points are
+    // enforced to be on the curve, s to be less than the order of the subgroup and the bits of the challenge
+    // to be canonical.
+    pub fn verify_eddsa(&mut self, public_key: PointWires, message: Wire, r: PointWires, s: Wire) {
+        self.enforce_on_curve(public_key);
+        self.enforce_on_curve(r);
+
+        let order = jubjub::order();
+        let s_bits = self.decompose(s, order.bits() as usize);
+        self.enforce_bits_less_than(&s_bits, &order);
+        let s_g = self.jubjub_fixed_base_mul(Point::GENERATOR, &s_bits);
+
+        let c = self.poseidon_hash(&[r.x, r.y, public_key.x, public_key.y, message]);
+        let c_bits = self.decompose_canonical(c);
+        let c_a = self.jubjub_scalar_mul(public_key, &c_bits);
+        let r_c_a = self.jubjub_add(r, c_a);
+
+        let [left, right] = [s_g, r_c_a].map(|mut point| {
+            for _ in 0..3 {
+                point = self.jubjub_double(point);
+            }
+            point
+        });
+        self.enforce_equal(left.x, right.x);
+        self.enforce_equal(left.y, right.y);
+    }
+
+    // Allocate the 255 bits of x with hints, enforced to be its canonical representation: with as many bits
+    // as Fr, x and x + r would both fit, so the bits must also be less than the modulus r
+    fn decompose_canonical(&mut self, x: Wire) -> Vec<Wire> {
+        let bits: Vec<Wire> = (0..Fr::MODULUS_BIT_SIZE as usize)
+            .map(|i| self.hint(HintOp::Bit(i), x))
+            .collect();
+        for &bit in &bits {
+            self.enforce_boolean(bit);
+        }
+        let recomposed = self.recompose(&bits);
+        self.enforce_equal(recomposed, x);
+        self.enforce_bits_less_than(&bits, &Fr::MODULUS.into());
+        bits
+    }
+
+    // Enforce that the integer with the given bits, from the least significant one, is less than bound.
+    // The bits must be 0 or 1. Scanning from the most significant bit, eq is 1 while the bits equal those
+    // of bound and le while they are less or equal: a 1 of bound only updates eq, a 0 of bound where the
+    // bits have a 1 clears both. The bits are less than bound if le - eq is 1 at the end.
+    fn enforce_bits_less_than(&mut self, bits: &[Wire], bound: &BigUint) {
+        let mut le = self.constant(Fr::ONE);
+        let mut eq = le;
+        for (i, &bit) in bits.iter().enumerate().rev() {
+            let eq_and_bit = self.mul(eq, bit);
+            if !bound.bit(i as u64) {
+                le = self.sub(le, eq_and_bit);
+                eq = self.sub(eq, eq_and_bit);
+            } else {
+                eq = eq_and_bit;
+            }
+        }
+        self.constrain(
+            Selectors {
+                qL: Fr::ONE,
+                qR: -Fr::ONE,
+                qC: -Fr::ONE,
+                ..Default::default()
+            },
+            Some(le),
+            Some(eq),
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::ops::{Add, Neg};
+
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field, MontFp};
+use num_bigint::BigUint;
+
+use crate::{
+    circuit::{builder::CircuitBuilder, Wire},
+    common::protocols::Selectors,
+};
+
+// Jubjub is the twisted Edwards curve -x^2 + y^2 = 1 + d*x^2*y^2 over Fr, with d = -10240/10241. Its points
+// form a group of order 8 * order(). The addition formulas are complete: they hold for any two points,
+// including equal points and the identity (0, 1).
+pub const D: Fr =
+    MontFp!("19257038036680949359750312669786877991949435402254120286184196891950884077233");
+
+// Order of the prime subgroup generated by Point::GENERATOR
+pub fn order() -> BigUint {
+    BigUint::parse_bytes(
+        b"0e7db4ea6533afa906673b0101343b00a6682093ccc81082d0970e5ed6f72cb7",
+        16,
+    )
+    .unwrap()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Point {
+    pub x: Fr,
+    pub y: Fr,
+}
+
+impl Point {
+    pub const IDENTITY: Point = Point {
+        x: Fr::ZERO,
+        y: Fr::ONE,
+    };
+
+    pub const GENERATOR: Point = Point {
+        x: MontFp!("8076246640662884909881801758704306714034609987455869804520522091855516602923"),
+        y: MontFp!("13262374693698910701929044844600465831413122818447359594527400194675274060458"),
+    };
+
+    pub fn is_on_curve(&self) -> bool {
+        let (xx, yy) = (self.x.square(), self.y.square());
+        yy - xx == Fr::ONE + D * xx * yy
+    }
+
+    pub fn double(&self) -> Point {
+        *self + *self
+    }
+
+    // scalar * self by double-and-add from the most significant bit
+    pub fn scalar_mul(&self, scalar: &BigUint) -> Point {
+        (0..scalar.bits()).rev().fold(Point::IDENTITY, |acc, i| {
+            let acc = acc.double();
+            if scalar.bit(i) {
+                acc + *self
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    // x3 = (x1*y2 + y1*x2) / (1 + d*x1*x2*y1*y2), y3 = (y1*y2 + x1*x2) / (1 - d*x1*x2*y1*y2)
+    fn add(self, other: Point) -> Point {
+        let (x1y2, y1x2) = (self.x * other.y, self.y * other.x);
+        let dxy = D * x1y2 * y1x2;
+        Point {
+            x: (x1y2 + y1x2) / (Fr::ONE + dxy),
+            y: (self.y * other.y + self.x * other.x) / (Fr::ONE - dxy),
+        }
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+}
+
+// Jubjub point in a circuit, as the wires of its coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PointWires {
+    pub x: Wire,
+    pub y: Wire,
+}
+
+impl CircuitBuilder {
+    // Point fixed to a constant
+    pub fn jubjub_constant(&mut self, point: Point) -> PointWires {
+        PointWires {
+            x: self.constant(point.x),
+            y: self.constant(point.y),
+        }
+    }
+
+    // Enforce -x^2 + y^2 = 1 + d*x^2*y^2
+    pub fn enforce_on_curve(&mut self, point: PointWires) {
+        let xx = self.mul(point.x, point.x);
+        let yy = self.mul(point.y, point.y);
+        // -xx + yy - d*xx*yy - 1 = 0
+        self.constrain(
+            Selectors {
+                qL: -Fr::ONE,
+                qR: Fr::ONE,
+                qM: -D,
+                qC: -Fr::ONE,
+                ..Default::default()
+            },
+            Some(xx),
+            Some(yy),
+        );
+    }
+
+    // Complete addition in 12 gates, the divisions being checked through the inverses of the denominators
+    pub fn jubjub_add(&mut self, p: PointWires, q: PointWires) -> PointWires {
+        let x1y2 = self.mul(p.x, q.y);
+        let y1x2 = self.mul(p.y, q.x);
+        let x1x2 = self.mul(p.x, q.x);
+        let y1y2 = self.mul(p.y, q.y);
+
+        // 1 + d*x1y2*y1x2 and 1 - d*x1y2*y1x2
+        let denominators = [D, -D].map(|k| {
+            self.gate(
+                Selectors {
+                    qO: -Fr::ONE,
+                    qM: k,
+                    qC: Fr::ONE,
+                    ..Default::default()
+                },
+                Some(x1y2),
+                Some(y1x2),
+            )
+        });
+        let x_numerator = self.add(x1y2, y1x2);
+        let y_numerator = self.add(y1y2, x1x2);
+        PointWires {
+            x: self.div(x_numerator, denominators[0]),
+            y: self.div(y_numerator, denominators[1]),
+        }
+    }
+
+    // Doubling in 10 gates: with the curve equation, x3 = 2xy / (y^2 - x^2) and y3 = (x^2 + y^2) / (2 + x^2 - y^2).
+    // The point must be on the curve.
+    pub fn jubjub_double(&mut self, p: PointWires) -> PointWires {
+        let xx = self.mul(p.x, p.x);
+        let yy = self.mul(p.y, p.y);
+        let xy2 = self.gate(
+            Selectors {
+                qO: -Fr::ONE,
+                qM: Fr::from(2),
+                ..Default::default()
+            },
+            Some(p.x),
+            Some(p.y),
+        );
+
+        let x_denominator = self.sub(yy, xx);
+        let y_numerator = self.add(xx, yy);
+        let y_denominator = self.linear_combination(xx, Fr::ONE, yy, -Fr::ONE, Fr::from(2));
+        PointWires {
+            x: self.div(xy2, x_denominator),
+            y: self.div(y_numerator, y_denominator),
+        }
+    }
+
+    // Return scalar * p, the scalar being given by its bits from the least significant one.
+    // The bits must be 0 or 1 and p on the curve. Every bit costs a doubling and an addition.
+    pub fn jubjub_scalar_mul(&mut self, p: PointWires, bits: &[Wire]) -> PointWires {
+        let mut power = p;
+        let mut acc: Option<PointWires> = None;
+        for (i, &bit) in bits.iter().enumerate() {
+            if i > 0 {
+                power = self.jubjub_double(power);
+            }
+            // (bit * x, bit * y - bit + 1) is power if bit is 1 and the identity otherwise
+            let x = self.mul(bit, power.x);
+            let y = self.gate(
+                Selectors {
+                    qL: -Fr::ONE,
+                    qO: -Fr::ONE,
+                    qM: Fr::ONE,
+                    qC: Fr::ONE,
+                    ..Default::default()
+                },
+                Some(bit),
+                Some(power.y),
+            );
+            acc = Some(match acc {
+                None => PointWires { x, y },
+                Some(acc) => self.jubjub_add(acc, PointWires { x, y }),
+            });
+        }
+        acc.unwrap_or_else(|| self.jubjub_constant(Point::IDENTITY))
+    }
+
+    // Return scalar * base for a constant base, the scalar being given by its bits from the least significant
+    // one, which must be 0 or 1. Every window of 2 bits selects one of the 4 precomputed multiples of
+    // 4^j * base with constant coefficients, then costs a single addition.
+    pub fn jubjub_fixed_base_mul(&mut self, base: Point, bits: &[Wire]) -> PointWires {
+        let mut acc: Option<PointWires> = None;
+        let mut window_base = base;
+        for window in bits.chunks(2) {
+            let multiples = [
+                Point::IDENTITY,
+                window_base,
+                window_base.double(),
+                window_base.double() + window_base,
+            ];
+            let point = match *window {
+                [b0, b1] => {
+                    // c0 + b0*(c1 - c0) + b1*(c2 - c0) + b0*b1*(c3 - c2 - c1 + c0) on each coordinate
+                    let b0b1 = self.mul(b0, b1);
+                    let [x, y] = [|p: Point| p.x, |p: Point| p.y].map(|coordinate| {
+                        let [c0, c1, c2, c3] = multiples.map(coordinate);
+                        let linear = self.gate(
+                            Selectors {
+                                qL: c1 - c0,
+                                qR: c2 - c0,
+                                qO: -Fr::ONE,
+                                qC: c0,
+                                ..Default::default()
+                            },
+                            Some(b0),
+                            Some(b1),
+                        );
+                        self.linear_combination(linear, Fr::ONE, b0b1, c3 - c2 - c1 + c0, Fr::ZERO)
+                    });
+                    PointWires { x, y }
+                }
+                // last bit alone: (b0 * x1, 1 + b0 * (y1 - 1))
+                [b0] => PointWires {
+                    x: self.mul_constant(b0, window_base.x),
+                    y: self.gate(
+                        Selectors {
+                            qL: window_base.y - Fr::ONE,
+                            qO: -Fr::ONE,
+                            qC: Fr::ONE,
+                            ..Default::default()
+                        },
+                        Some(b0),
+                        None,
+                    ),
+                },
+                _ => unreachable!(),
+            };
+            acc = Some(match acc {
+                None => point,
+                Some(acc) => self.jubjub_add(acc, point),
+            });
+            window_base = window_base.double().double();
+        }
+        acc.unwrap_or_else(|| self.jubjub_constant(Point::IDENTITY))
+    }
+}
@@ -0,0 +1,88 @@
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+
+use crate::{
+    circuit::{builder::CircuitBuilder, HintOp, Wire},
+    common::protocols::Selectors,
+};
+
+// Boolean operations expect inputs that are 0 or 1 (e.g. enforced with enforce_boolean),
+// their outputs are then 0 or 1 too
+impl CircuitBuilder {
+    // a * b
+    pub fn and(&mut self, a: Wire, b: Wire) -> Wire {
+        self.mul(a, b)
+    }
+
+    // a + b - a * b
+    pub fn or(&mut self, a: Wire, b: Wire) -> Wire {
+        self.boolean_gate(a, b, -Fr::ONE)
+    }
+
+    // a + b - 2 * a * b
+    pub fn xor(&mut self, a: Wire, b: Wire) -> Wire {
+        self.boolean_gate(a, b, -Fr::from(2))
+    }
+
+    // 1 - a
+    pub fn not(&mut self, a: Wire) -> Wire {
+        self.gate(
+            Selectors {
+                qL: -Fr::ONE,
+                qO: -Fr::ONE,
+                qC: Fr::ONE,
+                ..Default::default()
+            },
+            Some(a),
+            None,
+        )
+    }
+
+    // a if condition is 1, b if it is 0: b + condition * (a - b)
+    pub fn select(&mut self, condition: Wire, a: Wire, b: Wire) -> Wire {
+        let difference = self.sub(a, b);
+        let selected = self.mul(condition, difference);
+        self.add(b, selected)
+    }
+
+    // Return 1 if x is 0 and 0 otherwise, allocating the inverse of x (or 0) as a witness with a hint.
+    // out = 1 - x * inv and x * out = 0: if x is not 0, the second constraint sets out to 0,
+    // and the first one can only hold with inv = 1/x. If x is 0, out is 1 whatever inv is.
+    pub fn is_zero(&mut self, x: Wire) -> Wire {
+        let inverse = self.hint(HintOp::Inverse, x);
+        let out = self.gate(
+            Selectors {
+                qO: -Fr::ONE,
+                qM: -Fr::ONE,
+                qC: Fr::ONE,
+                ..Default::default()
+            },
+            Some(x),
+            Some(inverse),
+        );
+        self.constrain(
+            Selectors {
+                qM: Fr::ONE,
+                ..Default::default()
+            },
+            Some(x),
+            Some(out),
+        );
+        out
+    }
+
+    // a + b + k_ab * a * b
+    fn boolean_gate(&mut self, a: Wire, b: Wire, k_ab: Fr) -> Wire {
+        self.gate(
+            Selectors {
+                qL: Fr::ONE,
+                qR: Fr::ONE,
+                qO: -Fr::ONE,
+                qM: k_ab,
+                ..Default::default()
+            },
+            Some(a),
+            Some(b),
+        )
+    }
+}
@@ -0,0 +1,111 @@
+use ark_bls12_381::Fr;
+
+use crate::circuit::{builder::CircuitBuilder, gadgets::poseidon, Wire};
+
+// Binary Merkle tree over Fr, whose nodes are the Poseidon hash of their two children
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    // layers of nodes from the leaves to the root
+    layers: Vec<Vec<Fr>>,
+}
+
+impl MerkleTree {
+    // The number of leaves must be a power of two
+    pub fn new(leaves: Vec<Fr>) -> Self {
+        assert!(
+            leaves.len().is_power_of_two(),
+            "{} leaves do not fill a binary tree",
+            leaves.len()
+        );
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| compress(pair[0], pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> Fr {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    pub fn leaf(&self, index: usize) -> Fr {
+        self.layers[0][index]
+    }
+
+    // Siblings of the nodes from the leaf at index up to the root, excluded
+    pub fn path(&self, index: usize) -> Vec<Fr> {
+        self.layers[..self.depth()]
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| layer[(index >> level) ^ 1])
+            .collect()
+    }
+}
+
+pub fn compress(left: Fr, right: Fr) -> Fr {
+    poseidon::hash(&[left, right])
+}
+
+// Whether the path authenticates leaf at index under root
+pub fn verify_path(root: Fr, leaf: Fr, index: usize, path: &[Fr]) -> bool {
+    let computed = path
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (level, &sibling)| {
+            if (index >> level) & 1 == 0 {
+                compress(node, sibling)
+            } else {
+                compress(sibling, node)
+            }
+        });
+    computed == root
+}
+
+impl CircuitBuilder {
+    // Return the root computed from leaf and its path. The index of the leaf is given by its bits,
+    // from the least significant one: bit i is set if the node at level i is a right child.
+    // The bits are enforced to be booleans.
+    pub fn merkle_root(&mut self, leaf: Wire, index_bits: &[Wire], path: &[Wire]) -> Wire {
+        assert_eq!(
+            index_bits.len(),
+            path.len(),
+            "The index must have one bit per level of the path"
+        );
+
+        let mut node = leaf;
+        for (&bit, &sibling) in index_bits.iter().zip(path) {
+            self.enforce_boolean(bit);
+
+            // left = node + bit * (sibling - node), right = sibling - bit * (sibling - node)
+            let difference = self.sub(sibling, node);
+            let swap = self.mul(bit, difference);
+            let left = self.add(node, swap);
+            let right = self.sub(sibling, swap);
+            node = self.poseidon_hash(&[left, right]);
+        }
+        node
+    }
+
+    // Enforce that the path authenticates leaf under root
+    pub fn verify_merkle_path(
+        &mut self,
+        root: Wire,
+        leaf: Wire,
+        index_bits: &[Wire],
+        path: &[Wire],
+    ) {
+        let computed = self.merkle_root(leaf, index_bits, path);
+        self.enforce_equal(computed, root);
+    }
+}
@@ -0,0 +1,11 @@
+// Reusable pieces of circuits, added to a CircuitBuilder as methods.
+// Values that gates cannot compute, such as the bits of a value, are witnesses with a hint allocated by the
+// gadget, the prover does not give them.
+pub mod bits;
+pub mod comparison;
+pub mod eddsa;
+pub mod jubjub;
+pub mod logic;
+pub mod merkle;
+pub mod poseidon;
+pub mod sha256;
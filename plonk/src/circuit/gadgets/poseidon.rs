@@ -0,0 +1,226 @@
+use std::sync::OnceLock;
+
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, BigInteger, Field, PrimeField};
+
+use crate::{
+    circuit::{builder::CircuitBuilder, Wire},
+    common::protocols::Selectors,
+};
+
+// Poseidon permutation x^5 over Fr with a state of 3 elements, 8 full rounds and 57 partial rounds,
+// as in the reference implementation (poseidonperm_x5_255_3) of the Poseidon paper.
+pub const WIDTH: usize = 3;
+pub const FULL_ROUNDS: usize = 8;
+pub const PARTIAL_ROUNDS: usize = 57;
+
+pub struct Parameters {
+    // constants added to the state at the start of each round
+    pub round_constants: Vec<[Fr; WIDTH]>,
+    pub mds: [[Fr; WIDTH]; WIDTH],
+}
+
+// Parameters generated with the Grain LFSR, as done by generate_parameters_grain.sage
+pub fn parameters() -> &'static Parameters {
+    static PARAMETERS: OnceLock<Parameters> = OnceLock::new();
+    PARAMETERS.get_or_init(|| {
+        let mut grain = Grain::new();
+        let round_constants = (0..FULL_ROUNDS + PARTIAL_ROUNDS)
+            .map(|_| [(); WIDTH].map(|_| grain.next_field_element()))
+            .collect();
+
+        // Cauchy matrix 1 / (x_i + y_j), x and y are sampled without rejection
+        let xs = [(); WIDTH].map(|_| grain.next_field_element_reduced());
+        let ys = [(); WIDTH].map(|_| grain.next_field_element_reduced());
+        let mds = xs.map(|x| ys.map(|y| (x + y).inverse().unwrap()));
+
+        Parameters {
+            round_constants,
+            mds,
+        }
+    })
+}
+
+struct Grain {
+    state: [bool; 80],
+}
+
+impl Grain {
+    fn new() -> Self {
+        // field (prime field = 1), S-box (x^alpha = 0), field size, width, full rounds, partial rounds
+        // then 30 set bits
+        let fields = [
+            (1, 2),
+            (0, 4),
+            (Fr::MODULUS_BIT_SIZE as usize, 12),
+            (WIDTH, 12),
+            (FULL_ROUNDS, 10),
+            (PARTIAL_ROUNDS, 10),
+        ];
+        let mut bits = Vec::with_capacity(80);
+        for (value, size) in fields {
+            bits.extend((0..size).rev().map(|i| (value >> i) & 1 == 1));
+        }
+        bits.resize(80, true);
+
+        let mut grain = Grain {
+            state: bits.try_into().unwrap(),
+        };
+        for _ in 0..160 {
+            grain.step();
+        }
+        grain
+    }
+
+    fn step(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.rotate_left(1);
+        self.state[79] = bit;
+        bit
+    }
+
+    // Bits are output in pairs, the second one is kept only if the first one is set
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.step();
+            let bit = self.step();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    // Field size bits, most significant first
+    fn next_bits(&mut self) -> Vec<bool> {
+        (0..Fr::MODULUS_BIT_SIZE).map(|_| self.next_bit()).collect()
+    }
+
+    // Values that are not less than the modulus are rejected
+    fn next_field_element(&mut self) -> Fr {
+        loop {
+            let bigint = <Fr as PrimeField>::BigInt::from_bits_be(&self.next_bits());
+            if let Some(element) = Fr::from_bigint(bigint) {
+                return element;
+            }
+        }
+    }
+
+    fn next_field_element_reduced(&mut self) -> Fr {
+        let bigint = <Fr as PrimeField>::BigInt::from_bits_be(&self.next_bits());
+        Fr::from_le_bytes_mod_order(&bigint.to_bytes_le())
+    }
+}
+
+// Half of the full rounds are before the partial rounds, the other half after them
+fn is_full_round(round: usize) -> bool {
+    !(FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS).contains(&round)
+}
+
+pub fn permute(mut state: [Fr; WIDTH]) -> [Fr; WIDTH] {
+    let parameters = parameters();
+    for (round, constants) in parameters.round_constants.iter().enumerate() {
+        for (x, c) in state.iter_mut().zip(constants) {
+            *x += c;
+        }
+        let sboxes = if is_full_round(round) { WIDTH } else { 1 };
+        for x in &mut state[..sboxes] {
+            *x = x.pow([5]);
+        }
+        state = parameters
+            .mds
+            .map(|row| row.iter().zip(&state).map(|(m, x)| *m * x).sum());
+    }
+    state
+}
+
+// Sponge of rate 2 over the permutation: the first element of the state is the capacity, set to the
+// number of inputs to separate inputs of different lengths. The inputs are added to the two other
+// elements, two at a time, and the hash is the second element of the state.
+pub fn hash(inputs: &[Fr]) -> Fr {
+    let mut state = [Fr::from(inputs.len() as u64), Fr::ZERO, Fr::ZERO];
+    for chunk in inputs.chunks(WIDTH - 1) {
+        for (x, input) in state[1..].iter_mut().zip(chunk) {
+            *x += input;
+        }
+        state = permute(state);
+    }
+    state[1]
+}
+
+impl CircuitBuilder {
+    // Append the gates of the permutation and return the output state, in 3 gates per S-box and
+    // 2 gates per element of the state for the MDS matrix, i.e. 633 gates
+    pub fn poseidon_permutation(&mut self, mut state: [Wire; WIDTH]) -> [Wire; WIDTH] {
+        let parameters = parameters();
+        for (round, constants) in parameters.round_constants.iter().enumerate() {
+            // constants of the elements going through an S-box are added by the S-box gates,
+            // the other ones are added by the MDS matrix gates
+            let sboxes = if is_full_round(round) { WIDTH } else { 1 };
+            let mut offsets = *constants;
+            for (x, offset) in state[..sboxes].iter_mut().zip(&mut offsets) {
+                *x = self.sbox(*x, *offset);
+                *offset = Fr::ZERO;
+            }
+            state = self.mix(&parameters.mds, state, offsets);
+        }
+        state
+    }
+
+    // Same as hash, over wires
+    pub fn poseidon_hash(&mut self, inputs: &[Wire]) -> Wire {
+        let capacity = self.constant(Fr::from(inputs.len() as u64));
+        let zero = self.constant(Fr::ZERO);
+        let mut state = [capacity, zero, zero];
+        for (i, chunk) in inputs.chunks(WIDTH - 1).enumerate() {
+            for (x, &input) in state[1..].iter_mut().zip(chunk) {
+                // the rate elements are zero before the first permutation
+                *x = if i == 0 { input } else { self.add(*x, input) };
+            }
+            state = self.poseidon_permutation(state);
+        }
+        state[1]
+    }
+
+    // (x + c)^5
+    fn sbox(&mut self, x: Wire, c: Fr) -> Wire {
+        // (x + c)^2 = x^2 + 2c * x + c^2
+        let square = self.gate(
+            Selectors {
+                qL: c.double(),
+                qO: -Fr::ONE,
+                qM: Fr::ONE,
+                qC: c.square(),
+                ..Default::default()
+            },
+            Some(x),
+            Some(x),
+        );
+        let fourth = self.mul(square, square);
+        // (x + c)^4 * (x + c) = (x + c)^4 * x + c * (x + c)^4
+        self.gate(
+            Selectors {
+                qL: c,
+                qO: -Fr::ONE,
+                qM: Fr::ONE,
+                ..Default::default()
+            },
+            Some(fourth),
+            Some(x),
+        )
+    }
+
+    // mds * (state + offsets)
+    fn mix(
+        &mut self,
+        mds: &[[Fr; WIDTH]; WIDTH],
+        state: [Wire; WIDTH],
+        offsets: [Fr; WIDTH],
+    ) -> [Wire; WIDTH] {
+        mds.map(|row| {
+            let offset: Fr = row.iter().zip(&offsets).map(|(m, k)| *m * k).sum();
+            let partial = self.linear_combination(state[0], row[0], state[1], row[1], offset);
+            self.linear_combination(partial, Fr::ONE, state[2], row[2], Fr::ZERO)
+        })
+    }
+}
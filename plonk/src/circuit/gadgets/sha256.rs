@@ -0,0 +1,180 @@
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, BigInteger, Field, PrimeField};
+
+use crate::circuit::{builder::CircuitBuilder, Wire};
+
+// 32-bit word as its bits, from the least significant one. Operations expect bits that are 0 or 1.
+pub type Word = [Wire; 32];
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+impl CircuitBuilder {
+    // Return the SHA-256 digest of a message given as bits, in the order of the standard (most significant
+    // bit of each byte first). The message bits must be enforced to be 0 or 1 by the caller, its length
+    // is fixed by the circuit so that the padding is made of constants.
+    pub fn sha256(&mut self, message: &[Wire]) -> [Word; 8] {
+        assert!(
+            message.len().is_multiple_of(8),
+            "The message must be made of bytes"
+        );
+
+        // message, 1, zeros up to 448 mod 512, then the length on 64 bits
+        let zero = self.constant(Fr::ZERO);
+        let one = self.constant(Fr::ONE);
+        let length = message.len() as u64;
+        let mut padded = message.to_vec();
+        padded.push(one);
+        while padded.len() % 512 != 448 {
+            padded.push(zero);
+        }
+        padded.extend(
+            (0..64)
+                .rev()
+                .map(|i| if (length >> i) & 1 == 1 { one } else { zero }),
+        );
+
+        let mut state = IV.map(|h| self.constant_word(h));
+        for block in padded.chunks(512) {
+            // words are big-endian
+            let block: [Word; 16] =
+                std::array::from_fn(|t| std::array::from_fn(|i| block[32 * t + 31 - i]));
+            state = self.sha256_compression(&state, &block);
+        }
+        state
+    }
+
+    // Return the state after compressing a block of 16 words
+    pub fn sha256_compression(&mut self, state: &[Word; 8], block: &[Word; 16]) -> [Word; 8] {
+        // message schedule
+        let mut w: Vec<Word> = block.to_vec();
+        for t in 16..64 {
+            let shifted = self.shr(&w[t - 15], 3);
+            let s0 = self.xor3(rotr(&w[t - 15], 7), rotr(&w[t - 15], 18), shifted);
+            let shifted = self.shr(&w[t - 2], 10);
+            let s1 = self.xor3(rotr(&w[t - 2], 17), rotr(&w[t - 2], 19), shifted);
+            let sum = self.sum_words(&[&w[t - 16], &s0, &w[t - 7], &s1], 0);
+            w.push(self.truncate(sum, 4));
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+        for t in 0..64 {
+            let s1 = self.xor3(rotr(&e, 6), rotr(&e, 11), rotr(&e, 25));
+            let ch: Word = std::array::from_fn(|i| self.select(e[i], f[i], g[i]));
+            let t1 = self.sum_words(&[&h, &s1, &ch, &w[t]], K[t]);
+            let s0 = self.xor3(rotr(&a, 2), rotr(&a, 13), rotr(&a, 22));
+            let maj: Word = std::array::from_fn(|i| self.majority(a[i], b[i], c[i]));
+
+            let d_sum = self.sum_words(&[&d], 0);
+            let new_e = self.add(t1, d_sum);
+            let t2 = self.sum_words(&[&s0, &maj], 0);
+            let new_a = self.add(t1, t2);
+
+            (h, g, f) = (g, f, e);
+            e = self.truncate(new_e, 6);
+            (d, c, b) = (c, b, a);
+            a = self.truncate(new_a, 7);
+        }
+
+        let [h0, h1, h2, h3, h4, h5, h6, h7] = state;
+        [
+            (h0, a),
+            (h1, b),
+            (h2, c),
+            (h3, d),
+            (h4, e),
+            (h5, f),
+            (h6, g),
+            (h7, h),
+        ]
+        .map(|(x, y)| {
+            let sum = self.sum_words(&[x, &y], 0);
+            self.truncate(sum, 2)
+        })
+    }
+
+    // Return the value of a word as a field element
+    pub fn pack_word(&mut self, word: &Word) -> Wire {
+        self.recompose(word)
+    }
+
+    // Word whose bits are constants
+    pub fn constant_word(&mut self, value: u32) -> Word {
+        std::array::from_fn(|i| self.constant(Fr::from((value >> i) & 1)))
+    }
+
+    // sum(words) + k as a field element
+    fn sum_words(&mut self, words: &[&Word], k: u32) -> Wire {
+        let packed: Vec<Wire> = words.iter().map(|word| self.pack_word(word)).collect();
+        let mut sum = packed[0];
+        for &word in &packed[1..] {
+            sum = self.add(sum, word);
+        }
+        if k != 0 {
+            sum = self.add_constant(sum, Fr::from(k));
+        }
+        sum
+    }
+
+    // x mod 2^32 for a sum x of at most n words, through its 32 + log2(n) bits
+    fn truncate(&mut self, x: Wire, n: usize) -> Word {
+        let carry_bits = usize::BITS - (n - 1).leading_zeros();
+        let bits = self.decompose(x, 32 + carry_bits as usize);
+        std::array::from_fn(|i| bits[i])
+    }
+
+    fn xor3(&mut self, a: Word, b: Word, c: Word) -> Word {
+        std::array::from_fn(|i| {
+            let ab = self.xor(a[i], b[i]);
+            self.xor(ab, c[i])
+        })
+    }
+
+    // ab + c(a xor b), the two terms are never both 1
+    fn majority(&mut self, a: Wire, b: Wire, c: Wire) -> Wire {
+        let ab = self.and(a, b);
+        let a_xor_b = self.xor(a, b);
+        let c_and = self.and(c, a_xor_b);
+        self.add(ab, c_and)
+    }
+
+    fn shr(&mut self, word: &Word, n: usize) -> Word {
+        let zero = self.constant(Fr::ZERO);
+        std::array::from_fn(|i| if i + n < 32 { word[i + n] } else { zero })
+    }
+}
+
+fn rotr(word: &Word, n: usize) -> Word {
+    std::array::from_fn(|i| word[(i + n) % 32])
+}
+
+// Bits of a message in the order expected by sha256, to give as witnesses
+pub fn message_bits(message: &[u8]) -> Vec<Fr> {
+    message
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| Fr::from((byte >> i) & 1)))
+        .collect()
+}
+
+// Bytes of a digest from the values of its words
+pub fn digest_bytes(words: &[Fr]) -> Vec<u8> {
+    words
+        .iter()
+        .flat_map(|word| {
+            let bytes = word.into_bigint().to_bytes_le();
+            [bytes[3], bytes[2], bytes[1], bytes[0]]
+        })
+        .collect()
+}
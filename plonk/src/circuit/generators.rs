@@ -0,0 +1,131 @@
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+
+use crate::{
+    circuit::{builder::CircuitBuilder, witness::generate_trace, Circuit, CircuitError},
+    setup_global_params::{self, SetupGlobalParamsOutput},
+    setup_proving_key::{self, SetupProvingKeyOutput},
+    setup_verification_key::{self, SetupVerificationKeyOutput},
+};
+
+// Circuits whose size is a parameter, to measure how the setup, the prover and the verifier scale.
+// Each one comes with public inputs and witnesses satisfying it.
+#[derive(Clone, Debug)]
+pub struct GeneratedCircuit {
+    pub circuit: Circuit,
+    pub pub_inputs: Vec<Fr>,
+    pub witnesses: Vec<Fr>,
+}
+
+impl GeneratedCircuit {
+    // Evaluations of the trace T over Omega
+    pub fn trace(&self) -> Result<Vec<Fr>, CircuitError> {
+        generate_trace(&self.circuit, &self.pub_inputs, &self.witnesses)
+    }
+
+    // Values of the public outputs, read from the trace
+    pub fn outputs(&self) -> Result<Vec<Fr>, CircuitError> {
+        let trace = self.trace()?;
+        Ok(self
+            .circuit
+            .output_indices(trace.len())
+            .into_iter()
+            .map(|i| trace[i])
+            .collect())
+    }
+
+    // SRS, proving key and verification key of the circuit
+    pub fn setup(
+        &self,
+    ) -> (
+        SetupGlobalParamsOutput,
+        SetupProvingKeyOutput,
+        SetupVerificationKeyOutput,
+    ) {
+        let setup = setup_global_params::run(&self.circuit);
+        let proving_key = setup_proving_key::run(&setup, &self.circuit);
+        let verification_key = setup_verification_key::run(&setup, &self.circuit);
+        (setup, proving_key, verification_key)
+    }
+}
+
+// Public inputs f0 = f1 = 1, output f(n-1) of the Fibonacci sequence f(i) = f(i-1) + f(i-2), in n-2 gates
+pub fn fibonacci(n: usize) -> GeneratedCircuit {
+    assert!(n >= 3, "The sequence must have at least 3 terms");
+
+    let mut builder = CircuitBuilder::new();
+    let (mut previous, mut current) = (builder.public_input(), builder.public_input());
+    for _ in 2..n {
+        (previous, current) = (current, builder.add(previous, current));
+    }
+    builder.output(current);
+
+    GeneratedCircuit {
+        circuit: builder.build().unwrap(),
+        pub_inputs: vec![Fr::ONE, Fr::ONE],
+        witnesses: vec![],
+    }
+}
+
+// Public input x = 3, output x^(2^n) after n squarings, one gate each
+pub fn repeated_squaring(n: usize) -> GeneratedCircuit {
+    assert!(n >= 1, "At least one squaring is needed");
+
+    let mut builder = CircuitBuilder::new();
+    let mut x = builder.public_input();
+    for _ in 0..n {
+        x = builder.mul(x, x);
+    }
+    builder.output(x);
+
+    GeneratedCircuit {
+        circuit: builder.build().unwrap(),
+        pub_inputs: vec![Fr::from(3)],
+        witnesses: vec![],
+    }
+}
+
+// Public input x = 3, output x^e by square and multiply from the most significant bit of e:
+// a squaring per bit after the first one and a multiplication per other bit set
+pub fn exponentiation(e: u64) -> GeneratedCircuit {
+    assert!(e >= 2, "The exponent must be at least 2");
+
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let mut power = x;
+    for i in (0..e.ilog2()).rev() {
+        power = builder.mul(power, power);
+        if (e >> i) & 1 == 1 {
+            power = builder.mul(power, x);
+        }
+    }
+    builder.output(power);
+
+    GeneratedCircuit {
+        circuit: builder.build().unwrap(),
+        pub_inputs: vec![Fr::from(3)],
+        witnesses: vec![],
+    }
+}
+
+// Public vector a = (1, ..., n), witness vector b = (n, ..., 1), output the dot product of a and b
+// in 2n-1 gates
+pub fn dot_product(n: usize) -> GeneratedCircuit {
+    assert!(n >= 1, "The vectors must not be empty");
+
+    let mut builder = CircuitBuilder::new();
+    let a: Vec<_> = (0..n).map(|_| builder.public_input()).collect();
+    let b: Vec<_> = (0..n).map(|_| builder.witness()).collect();
+    let mut sum = builder.mul(a[0], b[0]);
+    for (&a_i, &b_i) in a.iter().zip(&b).skip(1) {
+        let product = builder.mul(a_i, b_i);
+        sum = builder.add(sum, product);
+    }
+    builder.output(sum);
+
+    GeneratedCircuit {
+        circuit: builder.build().unwrap(),
+        pub_inputs: (1..=n as u64).map(Fr::from).collect(),
+        witnesses: (1..=n as u64).rev().map(Fr::from).collect(),
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    circuit::{Circuit, CircuitError, Gate, Hint, HintOp, Wire},
+    common::{protocols::Selectors, utils::parse_field_element},
+};
+
+// Gate with op `add`, `sub` or `mul` on inputs left and right, op `const` with a constant value,
+// or op `gate` with explicit selectors and optional inputs
+#[derive(Serialize, Deserialize)]
+pub struct GateJson {
+    pub op: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right: Option<String>,
+    pub output: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selectors: Option<Selectors<String>>,
+}
+
+// Hint computing the witness output from the variable input, with op `inverse`, `sqrt`
+// or `bit` with the index of the bit
+#[derive(Serialize, Deserialize)]
+pub struct HintJson {
+    pub op: String,
+    pub input: String,
+    pub output: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CircuitJson {
+    pub public_inputs: Vec<String>,
+    pub witnesses: Vec<String>,
+    pub gates: Vec<GateJson>,
+    // Variables whose values are public outputs of the circuit
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    // Witnesses computed from other variables instead of being given by the prover
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<HintJson>,
+}
+
+impl CircuitJson {
+    pub fn into_circuit(self) -> Result<Circuit, CircuitError> {
+        // Map every declared variable name to the wire carrying its value
+        let mut variables = HashMap::new();
+        let declarations = self
+            .public_inputs
+            .iter()
+            .enumerate()
+            .map(|(j, name)| (name, Wire::PublicInput(j)))
+            .chain(
+                self.witnesses
+                    .iter()
+                    .enumerate()
+                    .map(|(k, name)| (name, Wire::Witness(k))),
+            )
+            .chain(
+                self.gates
+                    .iter()
+                    .enumerate()
+                    .map(|(l, gate)| (&gate.output, Wire::Gate(l))),
+            );
+        for (name, wire) in declarations {
+            if variables.insert(name.clone(), wire).is_some() {
+                return Err(CircuitError::DuplicateVariable(name.clone()));
+            }
+        }
+
+        let resolve = |name: &Option<String>| {
+            name.as_ref()
+                .map(|name| {
+                    variables
+                        .get(name)
+                        .copied()
+                        .ok_or_else(|| CircuitError::UnknownVariable(name.clone()))
+                })
+                .transpose()
+        };
+
+        let gates =
+            self.gates
+                .iter()
+                .map(|gate| {
+                    let (left, right) = (resolve(&gate.left)?, resolve(&gate.right)?);
+                    let parse = |value: &String| {
+                        parse_field_element(value)
+                            .ok_or_else(|| CircuitError::InvalidFieldElement(value.clone()))
+                    };
+                    match gate.op.as_str() {
+                        "add" | "sub" | "mul" => {
+                            let (Some(left), Some(right)) = (left, right) else {
+                                return Err(CircuitError::MissingGateInput(gate.output.clone()));
+                            };
+                            Ok(match gate.op.as_str() {
+                                "add" => Gate::addition(left, right),
+                                "sub" => Gate::subtraction(left, right),
+                                _ => Gate::multiplication(left, right),
+                            })
+                        }
+                        "const" => {
+                            let value = gate.value.as_ref().ok_or_else(|| {
+                                CircuitError::MissingConstantValue(gate.output.clone())
+                            })?;
+                            Ok(Gate::constant(parse(value)?))
+                        }
+                        "gate" => {
+                            let selectors = gate.selectors.as_ref().ok_or_else(|| {
+                                CircuitError::MissingSelectors(gate.output.clone())
+                            })?;
+                            let custom_gate = Gate {
+                                selectors: Selectors {
+                                    qL: parse(&selectors.qL)?,
+                                    qR: parse(&selectors.qR)?,
+                                    qO: parse(&selectors.qO)?,
+                                    qM: parse(&selectors.qM)?,
+                                    qC: parse(&selectors.qC)?,
+                                },
+                                left,
+                                right,
+                            };
+                            if custom_gate.reads_unwired_input() {
+                                return Err(CircuitError::UnwiredInputWithNonZeroSelector {
+                                    gate: gate.output.clone(),
+                                });
+                            }
+                            Ok(custom_gate)
+                        }
+                        op => Err(CircuitError::UnknownGateType(op.to_string())),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+        let mut outputs = vec![];
+        for name in &self.outputs {
+            let wire = variables
+                .get(name)
+                .copied()
+                .ok_or_else(|| CircuitError::UnknownVariable(name.clone()))?;
+            if outputs.contains(&wire) {
+                return Err(CircuitError::DuplicateOutput(name.clone()));
+            }
+            outputs.push(wire);
+        }
+
+        let mut hints: Vec<Hint> = vec![];
+        let mut hinted = HashSet::new();
+        for hint in &self.hints {
+            let input = variables
+                .get(&hint.input)
+                .copied()
+                .ok_or_else(|| CircuitError::UnknownVariable(hint.input.clone()))?;
+            let op = match (hint.op.as_str(), hint.bit) {
+                ("inverse", _) => HintOp::Inverse,
+                ("sqrt", _) => HintOp::SquareRoot,
+                ("bit", Some(i)) => HintOp::Bit(i),
+                (op, _) => return Err(CircuitError::UnknownHintType(op.to_string())),
+            };
+            let witness = match variables.get(&hint.output) {
+                Some(&Wire::Witness(k)) if hinted.insert(k) => k,
+                _ => return Err(CircuitError::InvalidHintOutput(hint.output.clone())),
+            };
+            hints.push(Hint { witness, op, input });
+        }
+
+        let circuit = Circuit {
+            number_public_inputs: self.public_inputs.len(),
+            number_witnesses: self.witnesses.len(),
+            gates,
+            outputs,
+            hints,
+        };
+        if let Some(l) = circuit.unconstrained_output_read() {
+            return Err(CircuitError::UnconstrainedOutputRead {
+                gate: self.gates[l].output.clone(),
+            });
+        }
+        Ok(circuit)
+    }
+}
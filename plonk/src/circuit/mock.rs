@@ -0,0 +1,57 @@
+use ark_bls12_381::Fr;
+use ark_ff::AdditiveGroup;
+use thiserror::Error;
+
+use crate::circuit::Circuit;
+
+// Constraint of the circuit that the trace does not satisfy
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ConstraintFailure {
+    #[error(
+        "gate#{l} at w^{}..w^{} is not satisfied: qL*a + qR*b + qO*c + qM*a*b + qC = {value} with a = {a}, b = {b}, c = {c}",
+        3 * l,
+        3 * l + 2
+    )]
+    Gate {
+        l: usize,
+        a: Fr,
+        b: Fr,
+        c: Fr,
+        value: Fr,
+    },
+    #[error("wiring cycle {cycle:?} carries different values [{}]", join(values))]
+    CopyConstraint { cycle: Vec<usize>, values: Vec<Fr> },
+}
+
+fn join(values: &[Fr]) -> String {
+    values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Check the trace (evaluations of T over Omega) against the circuit without committing to anything,
+// as the proof would: every gate must be satisfied and every wiring cycle must carry a single value.
+// Returns all the constraints that are violated.
+pub fn check_trace(circuit: &Circuit, trace: &[Fr]) -> Vec<ConstraintFailure> {
+    let d = trace.len();
+    let mut failures = vec![];
+
+    for (l, gate) in circuit.gates.iter().enumerate() {
+        let (a, b, c) = (trace[3 * l], trace[3 * l + 1], trace[3 * l + 2]);
+        let value = gate.selectors.evaluate_gate(a, b, c);
+        if value != Fr::ZERO {
+            failures.push(ConstraintFailure::Gate { l, a, b, c, value });
+        }
+    }
+
+    for cycle in circuit.wiring_cycles(d) {
+        if cycle.iter().any(|&i| trace[i] != trace[cycle[0]]) {
+            let values = cycle.iter().map(|&i| trace[i]).collect();
+            failures.push(ConstraintFailure::CopyConstraint { cycle, values });
+        }
+    }
+
+    failures
+}
@@ -0,0 +1,355 @@
+pub mod builder;
+pub mod circom;
+pub mod dot;
+pub mod dsl;
+pub mod gadgets;
+pub mod generators;
+pub mod json;
+pub mod mock;
+pub mod optimizer;
+pub mod relations;
+pub mod wiring;
+pub mod witness;
+
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, BigInteger, Field, PrimeField};
+use json::{CircuitJson, GateJson, HintJson};
+use mock::ConstraintFailure;
+use thiserror::Error;
+use wiring::CopyConstraints;
+
+use crate::common::{protocols::Selectors, utils::subgroup_size_at_least};
+
+#[derive(Debug, Error)]
+pub enum CircuitError {
+    #[error("Unknown gate type `{0}`, expected `add`, `sub`, `mul`, `const` or `gate`")]
+    UnknownGateType(String),
+    #[error("Variable `{0}` is declared more than once")]
+    DuplicateVariable(String),
+    #[error("Gate `{0}` is missing an input")]
+    MissingGateInput(String),
+    #[error("Gate `{0}` is missing its selectors")]
+    MissingSelectors(String),
+    #[error("Constant `{0}` is missing its value")]
+    MissingConstantValue(String),
+    #[error("Variable `{0}` is declared as output more than once")]
+    DuplicateOutput(String),
+    #[error("Variable `{0}` is used but never declared")]
+    UnknownVariable(String),
+    #[error("Expected {expected} {kind} values, got {got}")]
+    WrongNumberOfValues {
+        kind: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    #[error("Invalid field element `{0}`, expected decimal or 0x-prefixed hexadecimal")]
+    InvalidFieldElement(String),
+    #[error("Syntax error on line {line}: {message}")]
+    Syntax { line: usize, message: String },
+    #[error("Invalid circom file: {0}")]
+    InvalidCircomFile(String),
+    #[error("Constraint synthesis failed: {0}")]
+    Synthesis(#[from] ark_relations::r1cs::SynthesisError),
+    #[error("Trace does not satisfy the circuit: {}", join_failures(.0))]
+    UnsatisfiedConstraints(Vec<ConstraintFailure>),
+    #[error("Gates contain a cycle, gate#{0} depends on its own output")]
+    CyclicGates(usize),
+    #[error("Hint of witness#{0} depends on its own value")]
+    CyclicHint(usize),
+    #[error("Unknown hint type `{0}`, expected `inverse`, `sqrt` or `bit` with its index")]
+    UnknownHintType(String),
+    #[error("Hint output `{0}` must be a witness computed by no other hint")]
+    InvalidHintOutput(String),
+    #[error("Gate `{gate}` reads an input that is not wired, its selectors qL, qR or qM reading it must be zero")]
+    UnwiredInputWithNonZeroSelector { gate: String },
+    #[error("Gate `{gate}` has qO = 0 so its output is not constrained, no gate, hint or output may read it")]
+    UnconstrainedOutputRead { gate: String },
+}
+
+fn join_failures(failures: &[ConstraintFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| failure.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// Reference to a value of the circuit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Wire {
+    PublicInput(usize),
+    Witness(usize),
+    Gate(usize), // output of gate#l
+}
+
+// Gate enforcing qL*a + qR*b + qO*c + qM*a*b + qC = 0 on its inputs a = left, b = right and its output c.
+// An input that is not wired to any value is set to zero, nothing constrains it in the proof
+// so the selectors reading it (qL or qR, and qM) must be zero.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Gate {
+    pub selectors: Selectors<Fr>,
+    pub left: Option<Wire>,
+    pub right: Option<Wire>,
+}
+
+impl Gate {
+    // c = a + b
+    pub fn addition(a: Wire, b: Wire) -> Self {
+        Self::linear_combination(a, Fr::ONE, b, Fr::ONE, Fr::ZERO)
+    }
+
+    // c = a * b
+    pub fn multiplication(a: Wire, b: Wire) -> Self {
+        Gate {
+            selectors: Selectors {
+                qM: Fr::ONE,
+                qO: -Fr::ONE,
+                ..Default::default()
+            },
+            left: Some(a),
+            right: Some(b),
+        }
+    }
+
+    // c = a - b
+    pub fn subtraction(a: Wire, b: Wire) -> Self {
+        Self::linear_combination(a, Fr::ONE, b, -Fr::ONE, Fr::ZERO)
+    }
+
+    // c = k, the value of the constant is fixed by the selector qC committed in the verification key
+    pub fn constant(k: Fr) -> Self {
+        Gate {
+            selectors: Selectors {
+                qO: -Fr::ONE,
+                qC: k,
+                ..Default::default()
+            },
+            left: None,
+            right: None,
+        }
+    }
+
+    // c = k_a * a + k_b * b + k
+    pub fn linear_combination(a: Wire, k_a: Fr, b: Wire, k_b: Fr, k: Fr) -> Self {
+        Gate {
+            selectors: Selectors {
+                qL: k_a,
+                qR: k_b,
+                qO: -Fr::ONE,
+                qC: k,
+                ..Default::default()
+            },
+            left: Some(a),
+            right: Some(b),
+        }
+    }
+
+    // Name of the operation performed by the gate, `gate` if it matches none of the named operations
+    pub fn op(&self) -> &'static str {
+        match (self.left, self.right) {
+            (Some(a), Some(b)) if *self == Gate::addition(a, b) => "add",
+            (Some(a), Some(b)) if *self == Gate::subtraction(a, b) => "sub",
+            (Some(a), Some(b)) if *self == Gate::multiplication(a, b) => "mul",
+            (None, None) if *self == Gate::constant(self.selectors.qC) => "const",
+            _ => "gate",
+        }
+    }
+
+    // Whether a selector reads an input that is not wired: nothing constrains its slot in the trace,
+    // the prover could set it freely
+    pub fn reads_unwired_input(&self) -> bool {
+        let Selectors { qL, qR, qM, .. } = self.selectors;
+        (self.left.is_none() && (qL != Fr::ZERO || qM != Fr::ZERO))
+            || (self.right.is_none() && (qR != Fr::ZERO || qM != Fr::ZERO))
+    }
+
+    // Output c of the gate on inputs a,b: c = -(qL*a + qR*b + qM*a*b + qC) / qO.
+    // Gates with qO = 0 only constrain their inputs, their output is set to zero.
+    pub fn output(&self, a: Fr, b: Fr) -> Fr {
+        match self.selectors.qO.inverse() {
+            Some(qO_inverse) => -self.selectors.evaluate_gate(a, b, Fr::ZERO) * qO_inverse,
+            None => Fr::ZERO,
+        }
+    }
+}
+
+// Operation computing the value of a witness from the value of another wire
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HintOp {
+    // 1/x, or 0 if x is 0
+    Inverse,
+    // a square root of x, or 0 if x is not a square
+    SquareRoot,
+    // bit i of x
+    Bit(usize),
+}
+
+impl HintOp {
+    pub fn evaluate(&self, x: Fr) -> Fr {
+        match self {
+            HintOp::Inverse => x.inverse().unwrap_or(Fr::ZERO),
+            HintOp::SquareRoot => x.sqrt().unwrap_or(Fr::ZERO),
+            HintOp::Bit(i) => Fr::from(x.into_bigint().get_bit(*i)),
+        }
+    }
+}
+
+// Witness computed by the witness generator from the value of input, instead of being given by the prover.
+// The hint is not part of the proof: gates must check the witness, e.g. x * inv = 1 for an inverse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hint {
+    pub witness: usize,
+    pub op: HintOp,
+    pub input: Wire,
+}
+
+// Arithmetic circuit laid out over Omega as follows:
+// - gate#l reads its inputs at w^(3l), w^(3l+1) and writes its output at w^(3l+2)
+// - public input#j is at w^-(j+1)
+// - witness#k is at w^-(number_public_inputs+k+1)
+// The values of the output wires are public, they are opened by the proof.
+// Witnesses with a hint are computed from the other values, the prover only gives the other witnesses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Circuit {
+    pub number_public_inputs: usize,
+    pub number_witnesses: usize,
+    pub gates: Vec<Gate>,
+    pub outputs: Vec<Wire>,
+    pub hints: Vec<Hint>,
+}
+
+impl Circuit {
+    pub fn number_gates(&self) -> usize {
+        self.gates.len()
+    }
+
+    // Size of Omega: smallest subgroup holding all gate wires, public inputs and witnesses
+    pub fn d(&self) -> usize {
+        subgroup_size_at_least(
+            3 * self.number_gates() + self.number_public_inputs + self.number_witnesses,
+        )
+    }
+
+    // Witnesses given by the prover, i.e. without hint, in order
+    pub fn given_witnesses(&self) -> Vec<usize> {
+        let mut given = vec![true; self.number_witnesses];
+        for hint in &self.hints {
+            given[hint.witness] = false;
+        }
+        (0..self.number_witnesses).filter(|&k| given[k]).collect()
+    }
+
+    // First gate with qO = 0 whose output is read by a gate, a hint or an output of the circuit.
+    // Nothing constrains the output slot of such a gate, the prover could set it freely.
+    pub fn unconstrained_output_read(&self) -> Option<usize> {
+        self.gates
+            .iter()
+            .flat_map(|gate| gate.left.into_iter().chain(gate.right))
+            .chain(self.hints.iter().map(|hint| hint.input))
+            .chain(self.outputs.iter().copied())
+            .filter_map(|wire| match wire {
+                Wire::Gate(l) if self.gates[l].selectors.qO == Fr::ZERO => Some(l),
+                _ => None,
+            })
+            .min()
+    }
+
+    // Index in Omega of the slot holding the value of a wire
+    pub fn wire_index(&self, wire: Wire, d: usize) -> usize {
+        match wire {
+            Wire::PublicInput(j) => d - 1 - j,
+            Wire::Witness(k) => d - 1 - self.number_public_inputs - k,
+            Wire::Gate(l) => 3 * l + 2,
+        }
+    }
+
+    // Indices in Omega of the slots holding the public outputs
+    pub fn output_indices(&self, d: usize) -> Vec<usize> {
+        self.outputs
+            .iter()
+            .map(|&wire| self.wire_index(wire, d))
+            .collect()
+    }
+
+    // Evaluations of the selector polynomials on Omega_gates: Q(w^3l) = selectors of gate#l
+    pub fn selectors(&self) -> Vec<Selectors<Fr>> {
+        self.gates
+            .iter()
+            .map(|gate| gate.selectors.clone())
+            .collect()
+    }
+
+    // Copy constraints over Omega (as indices): every slot using a value is connected to the slot defining it
+    pub fn copy_constraints(&self, d: usize) -> CopyConstraints {
+        let mut copy_constraints = CopyConstraints::new(d);
+        for (l, gate) in self.gates.iter().enumerate() {
+            if let Some(left) = gate.left {
+                copy_constraints.connect(3 * l, self.wire_index(left, d));
+            }
+            if let Some(right) = gate.right {
+                copy_constraints.connect(3 * l + 1, self.wire_index(right, d));
+            }
+        }
+        copy_constraints
+    }
+
+    // Wiring cycles over Omega (as indices), each cycle gathering all slots carrying the same value
+    pub fn wiring_cycles(&self, d: usize) -> Vec<Vec<usize>> {
+        self.copy_constraints(d).cycles()
+    }
+}
+
+// Variable names x<j>, w<k> and g<l> are given to public inputs, witnesses and gate outputs
+pub fn convert_to_json_friendly_circuit(circuit: &Circuit) -> CircuitJson {
+    let name = |wire: Wire| match wire {
+        Wire::PublicInput(j) => format!("x{}", j),
+        Wire::Witness(k) => format!("w{}", k),
+        Wire::Gate(l) => format!("g{}", l),
+    };
+
+    CircuitJson {
+        public_inputs: (0..circuit.number_public_inputs)
+            .map(|j| name(Wire::PublicInput(j)))
+            .collect(),
+        witnesses: (0..circuit.number_witnesses)
+            .map(|k| name(Wire::Witness(k)))
+            .collect(),
+        gates: circuit
+            .gates
+            .iter()
+            .enumerate()
+            .map(|(l, gate)| {
+                // gates matching a named operation are written with it, other gates with their selectors
+                let op = gate.op();
+                GateJson {
+                    op: op.to_string(),
+                    left: gate.left.map(name),
+                    right: gate.right.map(name),
+                    output: name(Wire::Gate(l)),
+                    value: (op == "const").then(|| gate.selectors.qC.to_string()),
+                    selectors: (op == "gate")
+                        .then(|| gate.selectors.map(|selector| selector.to_string())),
+                }
+            })
+            .collect(),
+        outputs: circuit.outputs.iter().map(|&wire| name(wire)).collect(),
+        hints: circuit
+            .hints
+            .iter()
+            .map(|hint| HintJson {
+                op: match hint.op {
+                    HintOp::Inverse => "inverse",
+                    HintOp::SquareRoot => "sqrt",
+                    HintOp::Bit(_) => "bit",
+                }
+                .to_string(),
+                input: name(hint.input),
+                output: name(Wire::Witness(hint.witness)),
+                bit: match hint.op {
+                    HintOp::Bit(i) => Some(i),
+                    _ => None,
+                },
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,510 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field};
+
+use crate::{
+    circuit::{
+        builder::CircuitBuilder, witness::topological_order, Circuit, CircuitError, Gate, Hint,
+        Wire,
+    },
+    common::protocols::Selectors,
+};
+
+// Passes rewriting a circuit into an equivalent one with fewer gates, run before preprocessing since
+// the number of gates sets d, hence the degree of the SRS and the cost of the setup and the prover.
+// Every pass keeps the public inputs, the witnesses and the outputs (in order) of the circuit, and the hints
+// of its witnesses.
+// Gates with qO = 0 are constraints on their inputs, they are never removed unless trivially satisfied.
+pub type Pass = fn(&Circuit) -> Result<Circuit, CircuitError>;
+
+pub const PASSES: [(&str, Pass); 4] = [
+    ("constant folding", fold_constants),
+    (
+        "common subexpression elimination",
+        eliminate_common_subexpressions,
+    ),
+    ("linear gates merging", merge_linear_gates),
+    ("dead gates removal", remove_dead_gates),
+];
+
+// Number of gates before and after a pass
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PassReport {
+    pub pass: &'static str,
+    pub gates_before: usize,
+    pub gates_after: usize,
+}
+
+impl fmt::Display for PassReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} gates",
+            self.pass, self.gates_before, self.gates_after
+        )
+    }
+}
+
+// Run all passes in order
+pub fn optimize(circuit: &Circuit) -> Result<(Circuit, Vec<PassReport>), CircuitError> {
+    let mut circuit = circuit.clone();
+    let mut reports = vec![];
+    for (pass, run) in PASSES {
+        let optimized = run(&circuit)?;
+        reports.push(PassReport {
+            pass,
+            gates_before: circuit.number_gates(),
+            gates_after: optimized.number_gates(),
+        });
+        circuit = optimized;
+    }
+    Ok((circuit, reports))
+}
+
+// Replace the inputs of gates that are constants by their value in the selectors, e.g. x * 3 with
+// 3 = 1 + 2 is a single gate with qL = 3, and drop gates computing constants or copying their input.
+// Constants are only computed by a const gate when they are outputs.
+pub fn fold_constants(circuit: &Circuit) -> Result<Circuit, CircuitError> {
+    let mut builder = builder_with_inputs(circuit)?;
+    let mut values: HashMap<Wire, Value> = HashMap::new();
+
+    for l in topological_order(circuit)? {
+        let gate = &circuit.gates[l];
+        let value = |wire: Option<Wire>| match wire {
+            None => Value::Constant(Fr::ZERO),
+            Some(wire @ Wire::Gate(_)) => values[&wire],
+            Some(wire) => Value::Wire(wire),
+        };
+        let (a, b) = (value(gate.left), value(gate.right));
+
+        // qL*ka + qR*b + qM*ka*b + qC = qR'*b + qC' and similarly for b
+        let mut s = gate.selectors.clone();
+        if let Value::Constant(ka) = a {
+            s.qC += s.qL * ka;
+            s.qR += s.qM * ka;
+            (s.qL, s.qM) = (Fr::ZERO, Fr::ZERO);
+        }
+        if let Value::Constant(kb) = b {
+            s.qC += s.qR * kb;
+            s.qL += s.qM * kb;
+            (s.qR, s.qM) = (Fr::ZERO, Fr::ZERO);
+        }
+        let left = a.wire().filter(|_| s.qL != Fr::ZERO || s.qM != Fr::ZERO);
+        let right = b.wire().filter(|_| s.qR != Fr::ZERO || s.qM != Fr::ZERO);
+
+        let value = match (left, right) {
+            (None, None) if s.qO != Fr::ZERO => Value::Constant(-s.qC * s.qO.inverse().unwrap()),
+            (None, None) if s.qC == Fr::ZERO => Value::Constant(Fr::ZERO),
+            (Some(a), None) if s.qO != Fr::ZERO && s.qL == -s.qO && s.qC == Fr::ZERO => {
+                Value::Wire(a)
+            }
+            (None, Some(b)) if s.qO != Fr::ZERO && s.qR == -s.qO && s.qC == Fr::ZERO => {
+                Value::Wire(b)
+            }
+            _ => Value::Wire(builder.gate(s, left, right)),
+        };
+        values.insert(Wire::Gate(l), value);
+    }
+
+    let value = |wire: &Wire| match wire {
+        Wire::Gate(_) => values[wire],
+        _ => Value::Wire(*wire),
+    };
+    let outputs = circuit.outputs.iter().map(value).collect();
+    let hints = circuit
+        .hints
+        .iter()
+        .map(|hint| (*hint, value(&hint.input)))
+        .collect();
+    build_with_outputs(builder, outputs, hints)
+}
+
+// Compute only once gates reading the same inputs with the same selectors, up to the order of the inputs
+// and up to a factor for gates with qO != 0
+pub fn eliminate_common_subexpressions(circuit: &Circuit) -> Result<Circuit, CircuitError> {
+    let mut builder = builder_with_inputs(circuit)?;
+    let mut wires: HashMap<Wire, Wire> = HashMap::new();
+    let mut outputs_of_gates: HashMap<Gate, Wire> = HashMap::new();
+
+    for l in topological_order(circuit)? {
+        let gate = &circuit.gates[l];
+        let wire = |wire: Option<Wire>| wire.map(|wire| *wires.get(&wire).unwrap_or(&wire));
+        let (mut left, mut right) = (wire(gate.left), wire(gate.right));
+
+        // c = -(qL*a + qR*b + qM*a*b + qC) / qO, with inputs in a canonical order
+        let mut s = gate.selectors.clone();
+        if s.qO != Fr::ZERO {
+            let scale = -s.qO.inverse().unwrap();
+            s = s.map(|selector| *selector * scale);
+        }
+        if wire_order(right) < wire_order(left) {
+            (left, right) = (right, left);
+            (s.qL, s.qR) = (s.qR, s.qL);
+        }
+
+        let gate = Gate {
+            selectors: s,
+            left,
+            right,
+        };
+        let output = *outputs_of_gates
+            .entry(gate.clone())
+            .or_insert_with(|| builder.gate(gate.selectors, gate.left, gate.right));
+        wires.insert(Wire::Gate(l), output);
+    }
+
+    let value = |wire: &Wire| Value::Wire(*wires.get(wire).unwrap_or(wire));
+    let outputs = circuit.outputs.iter().map(value).collect();
+    let hints = circuit
+        .hints
+        .iter()
+        .map(|hint| (*hint, value(&hint.input)))
+        .collect();
+    build_with_outputs(builder, outputs, hints)
+}
+
+// Remove gates with qO != 0 whose output is neither an output of the circuit nor read by another gate or a hint
+pub fn remove_dead_gates(circuit: &Circuit) -> Result<Circuit, CircuitError> {
+    let mut live = vec![false; circuit.number_gates()];
+    let mut stack: Vec<usize> = (0..circuit.number_gates())
+        .filter(|&l| circuit.gates[l].selectors.qO == Fr::ZERO)
+        .chain(
+            circuit
+                .outputs
+                .iter()
+                .chain(circuit.hints.iter().map(|hint| &hint.input))
+                .filter_map(|wire| match wire {
+                    Wire::Gate(l) => Some(*l),
+                    _ => None,
+                }),
+        )
+        .collect();
+    while let Some(l) = stack.pop() {
+        if live[l] {
+            continue;
+        }
+        live[l] = true;
+        let gate = &circuit.gates[l];
+        for wire in [gate.left, gate.right] {
+            if let Some(Wire::Gate(m)) = wire {
+                stack.push(m);
+            }
+        }
+    }
+
+    // Live gates keep their order, their indices are shifted by the number of dead gates before them
+    let mut index = vec![0; circuit.number_gates()];
+    let mut number_live_gates = 0;
+    for l in 0..circuit.number_gates() {
+        index[l] = number_live_gates;
+        number_live_gates += live[l] as usize;
+    }
+    let wire = |wire: Wire| match wire {
+        Wire::Gate(l) => Wire::Gate(index[l]),
+        wire => wire,
+    };
+
+    Ok(Circuit {
+        number_public_inputs: circuit.number_public_inputs,
+        number_witnesses: circuit.number_witnesses,
+        gates: circuit
+            .gates
+            .iter()
+            .zip(&live)
+            .filter(|(_, &live)| live)
+            .map(|(gate, _)| {
+                let mut gate = gate.clone();
+                gate.left = gate.left.map(wire);
+                gate.right = gate.right.map(wire);
+                gate
+            })
+            .collect(),
+        outputs: circuit.outputs.iter().map(|&output| wire(output)).collect(),
+        hints: circuit
+            .hints
+            .iter()
+            .map(|hint| Hint {
+                input: wire(hint.input),
+                ..*hint
+            })
+            .collect(),
+    })
+}
+
+// Substitute linear gates (qM = 0) read by a single gate into it, when the result still reads at most two
+// wires, e.g. c = a + b then d = 2c - a is the single gate d = a + 2b
+pub fn merge_linear_gates(circuit: &Circuit) -> Result<Circuit, CircuitError> {
+    let mut uses: HashMap<Wire, usize> = HashMap::new();
+    for gate in &circuit.gates {
+        for wire in gate.left.iter().chain(gate.right.iter()) {
+            *uses.entry(*wire).or_default() += 1;
+        }
+    }
+    // outputs and inputs of hints must keep a wire
+    let hint_inputs = circuit.hints.iter().map(|hint| &hint.input);
+    for wire in circuit.outputs.iter().chain(hint_inputs) {
+        *uses.entry(*wire).or_default() += 2;
+    }
+
+    let mut builder = builder_with_inputs(circuit)?;
+    // Linear gates read once are pending: they are only added if they cannot be merged into their reader
+    let mut pending: HashMap<Wire, Affine> = HashMap::new();
+    let mut wires: HashMap<Wire, Wire> = HashMap::new();
+
+    for l in topological_order(circuit)? {
+        let gate = &circuit.gates[l];
+        let affine = |wire: Option<Wire>| match wire {
+            None => Affine::constant(Fr::ZERO),
+            Some(wire) => match pending.get(&wire) {
+                Some(affine) => affine.clone(),
+                None => Affine::wire(*wires.get(&wire).unwrap_or(&wire)),
+            },
+        };
+
+        let merged = match merge(&gate.selectors, &affine(gate.left), &affine(gate.right)) {
+            Some(merged) => merged,
+            // inputs that cannot be merged are added as gates
+            None => {
+                let mut wire = |wire: Option<Wire>| {
+                    wire.map(|wire| match pending.remove(&wire) {
+                        Some(affine) => affine.add_gate(&mut builder),
+                        None => *wires.get(&wire).unwrap_or(&wire),
+                    })
+                };
+                Gate {
+                    selectors: gate.selectors.clone(),
+                    left: wire(gate.left),
+                    right: wire(gate.right),
+                }
+            }
+        };
+        let (s, left, right) = (merged.selectors, merged.left, merged.right);
+
+        let output = Wire::Gate(l);
+        if s.qM == Fr::ZERO && s.qO != Fr::ZERO && uses.get(&output) == Some(&1) {
+            // c = -(qL*a + qR*b + qC) / qO
+            let scale = -s.qO.inverse().unwrap();
+            let mut affine = Affine::constant(s.qC * scale);
+            affine.add_term(left, s.qL * scale);
+            affine.add_term(right, s.qR * scale);
+            pending.insert(output, affine);
+        } else {
+            wires.insert(output, builder.gate(s, left, right));
+        }
+    }
+
+    let value = |wire: &Wire| Value::Wire(*wires.get(wire).unwrap_or(wire));
+    let outputs = circuit.outputs.iter().map(value).collect();
+    let hints = circuit
+        .hints
+        .iter()
+        .map(|hint| (*hint, value(&hint.input)))
+        .collect();
+    build_with_outputs(builder, outputs, hints)
+}
+
+// Value of a wire once constants are known
+#[derive(Clone, Copy, Debug)]
+enum Value {
+    Constant(Fr),
+    Wire(Wire),
+}
+
+impl Value {
+    fn wire(self) -> Option<Wire> {
+        match self {
+            Value::Constant(_) => None,
+            Value::Wire(wire) => Some(wire),
+        }
+    }
+}
+
+// Linear combination sum(k_i * wire_i) + k of distinct wires
+#[derive(Clone, Debug)]
+struct Affine {
+    terms: Vec<(Wire, Fr)>,
+    k: Fr,
+}
+
+impl Affine {
+    fn constant(k: Fr) -> Self {
+        Affine { terms: vec![], k }
+    }
+
+    fn wire(wire: Wire) -> Self {
+        Affine {
+            terms: vec![(wire, Fr::ONE)],
+            k: Fr::ZERO,
+        }
+    }
+
+    fn add_term(&mut self, wire: Option<Wire>, coefficient: Fr) {
+        let Some(wire) = wire else { return };
+        match self.terms.iter().position(|(w, _)| *w == wire) {
+            Some(i) => self.terms[i].1 += coefficient,
+            None => self.terms.push((wire, coefficient)),
+        }
+        self.terms
+            .retain(|(_, coefficient)| *coefficient != Fr::ZERO);
+    }
+
+    fn scaled(&self, scale: Fr) -> Self {
+        let mut affine = Affine::constant(self.k * scale);
+        for &(wire, coefficient) in &self.terms {
+            affine.add_term(Some(wire), coefficient * scale);
+        }
+        affine
+    }
+
+    fn sum(&self, other: &Self) -> Self {
+        let mut affine = self.clone();
+        affine.k += other.k;
+        for &(wire, coefficient) in &other.terms {
+            affine.add_term(Some(wire), coefficient);
+        }
+        affine
+    }
+
+    // Single term k_a * a + k, if there is at most one wire
+    fn single(&self) -> Option<(Option<Wire>, Fr, Fr)> {
+        match self.terms.as_slice() {
+            [] => Some((None, Fr::ZERO, self.k)),
+            [(wire, coefficient)] => Some((Some(*wire), *coefficient, self.k)),
+            _ => None,
+        }
+    }
+
+    // Add the gate computing the combination, which must have at most two wires
+    fn add_gate(&self, builder: &mut CircuitBuilder) -> Wire {
+        let term = |i: usize| self.terms.get(i).copied();
+        let ((left, qL), (right, qR)) = (
+            term(0).map_or((None, Fr::ZERO), |(w, k)| (Some(w), k)),
+            term(1).map_or((None, Fr::ZERO), |(w, k)| (Some(w), k)),
+        );
+        builder.gate(
+            Selectors {
+                qL,
+                qR,
+                qO: -Fr::ONE,
+                qC: self.k,
+                ..Default::default()
+            },
+            left,
+            right,
+        )
+    }
+}
+
+// Selectors and inputs of the gate qL*a + qR*b + qO*c + qM*a*b + qC = 0 with a and b replaced by
+// affine combinations, if the result reads at most two wires
+fn merge(s: &Selectors<Fr>, a: &Affine, b: &Affine) -> Option<Gate> {
+    if s.qM == Fr::ZERO {
+        let mut sum = a.scaled(s.qL).sum(&b.scaled(s.qR));
+        sum.k += s.qC;
+        if sum.terms.len() > 2 {
+            return None;
+        }
+        let term = |i: usize| sum.terms.get(i).copied();
+        let ((left, qL), (right, qR)) = (
+            term(0).map_or((None, Fr::ZERO), |(w, k)| (Some(w), k)),
+            term(1).map_or((None, Fr::ZERO), |(w, k)| (Some(w), k)),
+        );
+        let selectors = Selectors {
+            qL,
+            qR,
+            qO: s.qO,
+            qM: Fr::ZERO,
+            qC: sum.k,
+        };
+        return Some(Gate {
+            selectors,
+            left,
+            right,
+        });
+    }
+
+    // (k_a*a + ka) * (k_b*b + kb) expands into a gate on a and b
+    let (left, k_a, ka) = a.single()?;
+    let (right, k_b, kb) = b.single()?;
+    let selectors = Selectors {
+        qL: s.qL * k_a + s.qM * k_a * kb,
+        qR: s.qR * k_b + s.qM * ka * k_b,
+        qO: s.qO,
+        qM: s.qM * k_a * k_b,
+        qC: s.qL * ka + s.qR * kb + s.qM * ka * kb + s.qC,
+    };
+    Some(Gate {
+        selectors,
+        left,
+        right,
+    })
+}
+
+impl CircuitBuilder {
+    fn value(&mut self, value: Value) -> Wire {
+        match value {
+            Value::Constant(k) => self.constant(k),
+            Value::Wire(wire) => wire,
+        }
+    }
+}
+
+// Builder with the public inputs and the witnesses of the circuit, so that input wires are unchanged.
+// The circuit must not read the output of a gate with qO = 0, the passes could not preserve its value.
+fn builder_with_inputs(circuit: &Circuit) -> Result<CircuitBuilder, CircuitError> {
+    if let Some(l) = circuit.unconstrained_output_read() {
+        return Err(CircuitError::UnconstrainedOutputRead {
+            gate: format!("gate#{}", l),
+        });
+    }
+
+    let mut builder = CircuitBuilder::new();
+    for _ in 0..circuit.number_public_inputs {
+        builder.public_input();
+    }
+    for _ in 0..circuit.number_witnesses {
+        builder.witness();
+    }
+    Ok(builder)
+}
+
+// Declare the outputs, copying a wire when two outputs end up with the same value, and set the hints
+// to read the new values of their inputs
+fn build_with_outputs(
+    mut builder: CircuitBuilder,
+    outputs: Vec<Value>,
+    hints: Vec<(Hint, Value)>,
+) -> Result<Circuit, CircuitError> {
+    let mut declared = HashSet::new();
+    for output in outputs {
+        let mut wire = builder.value(output);
+        if !declared.insert(wire) {
+            wire = builder.mul_constant(wire, Fr::ONE);
+            declared.insert(wire);
+        }
+        builder.output(wire);
+    }
+    let hints = hints
+        .into_iter()
+        .map(|(hint, input)| Hint {
+            input: builder.value(input),
+            ..hint
+        })
+        .collect();
+    Ok(Circuit {
+        hints,
+        ..builder.build()?
+    })
+}
+
+// Order of wires used to sort the inputs of gates, missing inputs last
+fn wire_order(wire: Option<Wire>) -> (usize, usize) {
+    match wire {
+        Some(Wire::PublicInput(j)) => (0, j),
+        Some(Wire::Witness(k)) => (1, k),
+        Some(Wire::Gate(l)) => (2, l),
+        None => (3, 0),
+    }
+}
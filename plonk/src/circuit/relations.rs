@@ -0,0 +1,52 @@
+use ark_bls12_381::Fr;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, Matrix};
+
+use crate::circuit::{
+    circom::{LinearCombination, R1cs, R1csConstraint},
+    CircuitError,
+};
+
+// Run an arkworks ConstraintSynthesizer and return its R1CS with the values of all its variables.
+// Variables are ordered as in the matrices of ark-relations: the constant 1, the instance variables
+// then the witness variables. Instance variables become public inputs and witness variables witnesses,
+// so that R1cs::into_circuit and R1cs::prover_inputs give the circuit and the inputs of the prover.
+pub fn synthesize<C: ConstraintSynthesizer<Fr>>(
+    synthesizer: C,
+) -> Result<(R1cs, Vec<Fr>), CircuitError> {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    synthesizer.generate_constraints(cs.clone())?;
+    cs.finalize();
+
+    let matrices = cs
+        .to_matrices()
+        .expect("matrices are constructed in the default synthesis mode");
+    let cs = cs.borrow().unwrap();
+
+    let row = |matrix: &Matrix<Fr>, i: usize| -> LinearCombination {
+        matrix[i]
+            .iter()
+            .map(|&(coefficient, variable)| (variable, coefficient))
+            .collect()
+    };
+    let r1cs = R1cs {
+        number_wires: matrices.num_instance_variables + matrices.num_witness_variables,
+        number_public_outputs: 0,
+        number_public_inputs: matrices.num_instance_variables - 1,
+        number_private_inputs: matrices.num_witness_variables,
+        constraints: (0..matrices.num_constraints)
+            .map(|i| R1csConstraint {
+                a: row(&matrices.a, i),
+                b: row(&matrices.b, i),
+                c: row(&matrices.c, i),
+            })
+            .collect(),
+    };
+
+    let assignment = [
+        cs.instance_assignment.as_slice(),
+        cs.witness_assignment.as_slice(),
+    ]
+    .concat();
+
+    Ok((r1cs, assignment))
+}
@@ -0,0 +1,92 @@
+use ark_bls12_381::Fr;
+use ark_poly::{univariate::DensePolynomial, Polynomial};
+
+use crate::common::polynomials::interpolate_polynomial;
+
+// Copy constraints over the d slots of the trace: slots connected together carry the same variable
+#[derive(Clone, Debug)]
+pub struct CopyConstraints {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl CopyConstraints {
+    // No copy constraint yet: every slot is in its own class
+    pub fn new(d: usize) -> Self {
+        CopyConstraints {
+            parent: (0..d).collect(),
+            size: vec![1; d],
+        }
+    }
+
+    pub fn d(&self) -> usize {
+        self.parent.len()
+    }
+
+    // Representative of the class of slot i
+    fn find(&self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            i = self.parent[i];
+        }
+        i
+    }
+
+    // Record that slots a and b carry the same variable
+    pub fn connect(&mut self, a: usize, b: usize) {
+        let (mut root_a, mut root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return;
+        }
+        // attach the smallest class to the largest one to keep classes shallow
+        if self.size[root_a] < self.size[root_b] {
+            std::mem::swap(&mut root_a, &mut root_b);
+        }
+        self.parent[root_b] = root_a;
+        self.size[root_a] += self.size[root_b];
+    }
+
+    pub fn are_connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // Cycles of the wiring permutation, each one listing in increasing order the slots of a class.
+    // Slots that are not connected to any other slot are fixed points.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut cycles: Vec<Vec<usize>> = vec![];
+        let mut cycle_of_root = vec![usize::MAX; self.d()];
+        for i in 0..self.d() {
+            let root = self.find(i);
+            if cycle_of_root[root] == usize::MAX {
+                cycle_of_root[root] = cycles.len();
+                cycles.push(vec![]);
+            }
+            cycles[cycle_of_root[root]].push(i);
+        }
+        cycles
+    }
+
+    // Wiring permutation sigma over slots: sigma maps every slot to the next slot of its cycle
+    pub fn permutation(&self) -> Vec<usize> {
+        let mut sigma = vec![0; self.d()];
+        for cycle in self.cycles() {
+            for (i, &slot) in cycle.iter().enumerate() {
+                sigma[slot] = cycle[(i + 1) % cycle.len()];
+            }
+        }
+        sigma
+    }
+
+    // Interpolate the polynomial W such that W(w^i) = w^sigma(i) on Omega
+    pub fn wiring_polynomial(&self, Omega: &[Fr]) -> DensePolynomial<Fr> {
+        let d = self.d();
+        assert_eq!(Omega.len(), d, "Omega must be of length d");
+
+        let W_x_vals = Omega.to_vec();
+        let W_y_vals = self.permutation().iter().map(|&j| Omega[j]).collect();
+
+        let W = interpolate_polynomial(&W_x_vals, &W_y_vals);
+        assert!(W.degree() < d, "W must be of degree at most d-1");
+
+        W
+    }
+}
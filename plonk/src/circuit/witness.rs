@@ -0,0 +1,179 @@
+use ark_bls12_381::Fr;
+use ark_ff::AdditiveGroup;
+
+use crate::circuit::{Circuit, CircuitError, Wire};
+
+// Order gates such that every gate comes after the gates computing its inputs (Kahn's algorithm)
+pub fn topological_order(circuit: &Circuit) -> Result<Vec<usize>, CircuitError> {
+    let dependencies: Vec<Vec<usize>> = circuit
+        .gates
+        .iter()
+        .map(|gate| {
+            [gate.left, gate.right]
+                .into_iter()
+                .filter_map(|wire| match wire {
+                    Some(Wire::Gate(m)) => Some(m),
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect();
+    order(&dependencies).map_err(CircuitError::CyclicGates)
+}
+
+// Computation of a value of the trace: the output of a gate or a witness with a hint
+enum Step {
+    Gate(usize),
+    Hint(usize),
+}
+
+// Order gates and hints such that every value comes after the values it is computed from
+fn evaluation_order(circuit: &Circuit) -> Result<Vec<Step>, CircuitError> {
+    // gate#l is node l, the hint#h is node number_gates + h
+    let number_gates = circuit.number_gates();
+    let mut hint_nodes = vec![None; circuit.number_witnesses];
+    for (h, hint) in circuit.hints.iter().enumerate() {
+        hint_nodes[hint.witness] = Some(number_gates + h);
+    }
+    let node = |wire: Wire| match wire {
+        Wire::Gate(m) => Some(m),
+        Wire::Witness(k) => hint_nodes[k],
+        Wire::PublicInput(_) => None,
+    };
+
+    let dependencies: Vec<Vec<usize>> = circuit
+        .gates
+        .iter()
+        .map(|gate| {
+            [gate.left, gate.right]
+                .into_iter()
+                .flatten()
+                .filter_map(node)
+                .collect()
+        })
+        .chain(
+            circuit
+                .hints
+                .iter()
+                .map(|hint| node(hint.input).into_iter().collect()),
+        )
+        .collect();
+
+    match order(&dependencies) {
+        Ok(order) => Ok(order
+            .into_iter()
+            .map(|n| match n.checked_sub(number_gates) {
+                Some(h) => Step::Hint(h),
+                None => Step::Gate(n),
+            })
+            .collect()),
+        Err(n) if n < number_gates => Err(CircuitError::CyclicGates(n)),
+        Err(n) => Err(CircuitError::CyclicHint(
+            circuit.hints[n - number_gates].witness,
+        )),
+    }
+}
+
+// Order nodes such that every node comes after its dependencies, or return a node depending
+// (indirectly) on itself
+fn order(dependencies: &[Vec<usize>]) -> Result<Vec<usize>, usize> {
+    let number_nodes = dependencies.len();
+
+    // number of dependencies of node n, and nodes depending on node n
+    let mut number_dependencies = vec![0; number_nodes];
+    let mut dependents = vec![vec![]; number_nodes];
+    for (n, node_dependencies) in dependencies.iter().enumerate() {
+        for &m in node_dependencies {
+            number_dependencies[n] += 1;
+            dependents[m].push(n);
+        }
+    }
+
+    let mut order: Vec<usize> = (0..number_nodes)
+        .filter(|&n| number_dependencies[n] == 0)
+        .collect();
+    let mut next = 0;
+    while next < order.len() {
+        let n = order[next];
+        next += 1;
+        for &m in &dependents[n] {
+            number_dependencies[m] -= 1;
+            if number_dependencies[m] == 0 {
+                order.push(m);
+            }
+        }
+    }
+
+    // nodes left over depend on a cycle, follow their dependencies left over until one is met twice
+    let Some(mut n) = (0..number_nodes).find(|&n| number_dependencies[n] > 0) else {
+        return Ok(order);
+    };
+    let mut visited = vec![false; number_nodes];
+    while !visited[n] {
+        visited[n] = true;
+        n = *dependencies[n]
+            .iter()
+            .find(|&&m| number_dependencies[m] > 0)
+            .unwrap();
+    }
+    Err(n)
+}
+
+// Compute the evaluations of the trace T over Omega from the public inputs and witnesses.
+// Slots that are not used by the circuit (padding of Omega) are set to zero.
+pub fn generate_trace(
+    circuit: &Circuit,
+    pub_inputs: &[Fr],
+    witnesses: &[Fr],
+) -> Result<Vec<Fr>, CircuitError> {
+    if pub_inputs.len() != circuit.number_public_inputs {
+        return Err(CircuitError::WrongNumberOfValues {
+            kind: "public input",
+            expected: circuit.number_public_inputs,
+            got: pub_inputs.len(),
+        });
+    }
+    // witnesses with a hint are computed below
+    let given_witnesses = circuit.given_witnesses();
+    if witnesses.len() != given_witnesses.len() {
+        return Err(CircuitError::WrongNumberOfValues {
+            kind: "witness",
+            expected: given_witnesses.len(),
+            got: witnesses.len(),
+        });
+    }
+
+    let d = circuit.d();
+    let mut trace = vec![Fr::ZERO; d];
+
+    // T encodes all inputs: T(w^-j) = input#j, followed by the witnesses
+    for (j, input) in pub_inputs.iter().enumerate() {
+        trace[circuit.wire_index(Wire::PublicInput(j), d)] = *input;
+    }
+    for (&k, witness) in given_witnesses.iter().zip(witnesses) {
+        trace[circuit.wire_index(Wire::Witness(k), d)] = *witness;
+    }
+
+    // T encodes all wires of the gates: T(w^3l), T(w^3l+1) are the inputs of gate#l, T(w^3l+2) its output.
+    // Inputs of a gate that are not wired to any value are set to zero.
+    for step in evaluation_order(circuit)? {
+        let value =
+            |wire: Option<Wire>| wire.map_or(Fr::ZERO, |wire| trace[circuit.wire_index(wire, d)]);
+        match step {
+            Step::Gate(l) => {
+                let gate = &circuit.gates[l];
+                let (left, right) = (value(gate.left), value(gate.right));
+                trace[3 * l] = left;
+                trace[3 * l + 1] = right;
+                trace[3 * l + 2] = gate.output(left, right);
+            }
+            Step::Hint(h) => {
+                let hint = &circuit.hints[h];
+                let witness = hint.op.evaluate(value(Some(hint.input)));
+                trace[circuit.wire_index(Wire::Witness(hint.witness), d)] = witness;
+            }
+        }
+    }
+
+    Ok(trace)
+}
@@ -1,5 +1,5 @@
 use ark_bls12_381::{Bls12_381, Fr, G1Projective as G1, G2Projective as G2};
-use ark_ec::{pairing::Pairing, PrimeGroup};
+use ark_ec::{pairing::Pairing, scalar_mul::ScalarMul, CurveGroup, PrimeGroup, VariableBaseMSM};
 use ark_ff::{Field, UniformRand};
 use ark_poly::{
     univariate::{DenseOrSparsePolynomial, DensePolynomial},
@@ -26,14 +26,16 @@ pub fn kzg_setup(degree: usize) -> GlobalParameters {
 
     // Sample uniformly a random tau
     let tau = Fr::rand(&mut rng);
-    let mut accumulator = G1::generator();
-    let mut tau_powers_g1 = vec![accumulator];
 
-    // Compute tau^i * g1 for all i in the range [1..degree]
-    for _ in 1..(degree + 1) {
-        accumulator = accumulator * tau; // Update accumulator with tau^i
-        tau_powers_g1.push(accumulator); // Push tau^i into tau_powers_g1
-    }
+    // Compute tau^i * g1 for all i in the range [0..degree] with a fixed-base table of g1
+    let tau_powers: Vec<Fr> = std::iter::successors(Some(Fr::ONE), |power| Some(*power * tau))
+        .take(degree + 1)
+        .collect();
+    let tau_powers_g1 = G1::generator()
+        .batch_mul(&tau_powers)
+        .into_iter()
+        .map(G1::from)
+        .collect();
 
     // Compute tau * g2
     let tau_g2 = G2::generator() * tau;
@@ -49,13 +51,8 @@ pub fn kzg_commit(gp: &GlobalParameters, f: &DensePolynomial<Fr>) -> Result<G1,
     if gp.tau_powers_g1.len() < f.degree() + 1 {
         Err(CommitError::CommitFailed)
     } else {
-        // compute g1*f(tau)
-        Ok(f.coeffs
-            .iter()
-            .enumerate()
-            .map(|(i, f_i)| gp.tau_powers_g1[i] * f_i)
-            .reduce(|com_f, f_i_tau_i_g1| com_f + f_i_tau_i_g1)
-            .unwrap())
+        // compute g1*f(tau), the zero polynomial commits to the identity
+        Ok(msm_tau_powers(gp, &f.coeffs))
     }
 }
 
@@ -75,18 +72,18 @@ pub fn kzg_evaluate(gp: &GlobalParameters, f: &DensePolynomial<Fr>, u: Fr) -> (F
         DenseOrSparsePolynomial::divide_with_q_and_r(&(&f_minus_v).into(), &(&x_minus_u).into())
             .unwrap();
 
-    // compute proof as g1*q(tau)
-    let proof = q
-        .coeffs
-        .iter()
-        .enumerate()
-        .map(|(i, q_i)| gp.tau_powers_g1[i] * q_i)
-        .reduce(|proof, q_i_tau_i_g1| proof + q_i_tau_i_g1)
-        .unwrap();
+    // compute proof as g1*q(tau), q is the zero polynomial when f is constant
+    let proof = msm_tau_powers(gp, &q.coeffs);
 
     (v, proof)
 }
 
+// Compute sum(coeffs[i] * tau^i * g1) as a multi-scalar multiplication
+fn msm_tau_powers(gp: &GlobalParameters, coeffs: &[Fr]) -> G1 {
+    let bases = G1::normalize_batch(&gp.tau_powers_g1[..coeffs.len()]);
+    G1::msm_unchecked(&bases, coeffs)
+}
+
 // Verify the proof that committed polynomial f evaluates to v on point u
 pub fn kzg_verify(gp: &GlobalParameters, com_f: G1, u: Fr, v: Fr, proof: G1) -> bool {
     // compute left-hand side of pairing equality
@@ -1,10 +1,12 @@
 use ark_bls12_381::Fr;
-use ark_ff::Field;
+use ark_ff::{batch_inversion, AdditiveGroup, Field};
 use ark_poly::polynomial::univariate::*;
-use ark_poly::DenseUVPolynomial;
+use ark_poly::{DenseUVPolynomial, Polynomial};
 use ark_std::{rand::Rng, Zero};
 use rayon::prelude::*;
 
+use crate::common::utils::construct_vanishing_polynomial_from_roots;
+
 /// Generates a random polynomial of certain degree
 pub fn random_polynomial(rng: &mut impl Rng, degree: usize) -> DensePolynomial<Fr> {
     DensePolynomial::<Fr>::rand(degree, rng)
@@ -47,7 +49,12 @@ fn calculate_lagrange_polynomials(x_points: &Vec<Fr>) -> Vec<DensePolynomial<Fr>
 }
 
 /// Interpolates a polynomial from given evaluations at points using Lagrange interpolation.
+/// Points 1, q, q^2, ... such as a subgroup Omega take a fast path with FFTs.
 pub fn interpolate_polynomial(x_points: &Vec<Fr>, y_points: &Vec<Fr>) -> DensePolynomial<Fr> {
+    if let Some(q) = geometric_ratio(x_points) {
+        return interpolate_polynomial_geometric(q, y_points);
+    }
+
     let n = x_points.len();
     let lagrange_polys = calculate_lagrange_polynomials(&x_points);
 
@@ -57,6 +64,130 @@ pub fn interpolate_polynomial(x_points: &Vec<Fr>, y_points: &Vec<Fr>) -> DensePo
         .reduce(|| DensePolynomial::zero(), |result, value| result + &value)
 }
 
+/// Ratio q of points 1, q, q^2, ..., if there are at least two of them
+fn geometric_ratio(x_points: &[Fr]) -> Option<Fr> {
+    let q = *x_points.get(1)?;
+    (x_points[0] == Fr::ONE && x_points.windows(2).all(|pair| pair[1] == pair[0] * q)).then_some(q)
+}
+
+/// Interpolates the polynomial of degree < n taking values y_points on 1, q, ..., q^(n-1), in O(n log^2 n)
+pub fn interpolate_polynomial_geometric(q: Fr, y_points: &[Fr]) -> DensePolynomial<Fr> {
+    let n = y_points.len();
+
+    // On a subgroup of order n, the coefficients are the evaluations on the powers of 1/q divided by n
+    if q.pow([n as u64]) == Fr::ONE {
+        let y = DensePolynomial::from_coefficients_slice(y_points);
+        let n_inverse = Fr::from(n as u64).inverse().unwrap();
+        let coeffs = evaluate_polynomial_geometric(&y, q.inverse().unwrap(), n);
+        return DensePolynomial::from_coefficients_vec(
+            coeffs.into_iter().map(|c| c * n_inverse).collect(),
+        );
+    }
+
+    // f = P * sum(c_i / (x - q^i)) with P = prod(x - q^i) and c_i = y_i / P'(q^i).
+    // P'(q^i) = q^(i(i-1)/2 + i(n-1-i)) * (-1)^(n-1-i) * D_i * D_(n-1-i) with D_m = prod_{1<=l<=m} (q^l - 1)
+    let mut D = vec![Fr::ONE];
+    let mut q_l = Fr::ONE;
+    for _ in 1..n {
+        q_l *= q;
+        D.push(*D.last().unwrap() * (q_l - Fr::ONE));
+    }
+    let mut c: Vec<Fr> = (0..n)
+        .map(|i| {
+            let (i, m) = (i as u64, (n - 1 - i) as u64);
+            let sign = if m % 2 == 0 { Fr::ONE } else { -Fr::ONE };
+            q.pow([i * i.saturating_sub(1) / 2 + i * m]) * sign * D[i as usize] * D[m as usize]
+        })
+        .collect();
+    batch_inversion(&mut c);
+    c.iter_mut()
+        .zip(y_points)
+        .for_each(|(c_i, y_i)| *c_i *= y_i);
+
+    // sum(c_i / (x - q^i)) = sum_m s_m x^(-m-1) with s_m = sum_i c_i q^(im) = C(q^m),
+    // so the coefficient t of f is sum_m p_(t+1+m) s_m
+    let s = evaluate_polynomial_geometric(&DensePolynomial::from_coefficients_vec(c), q, n);
+    let roots = std::iter::successors(Some(Fr::ONE), |root| Some(*root * q))
+        .take(n)
+        .collect();
+    let P = construct_vanishing_polynomial_from_roots(&roots);
+    let s_reversed = DensePolynomial::from_coefficients_vec(s.into_iter().rev().collect());
+    let product = &s_reversed * &P;
+    DensePolynomial::from_coefficients_vec(
+        (0..n)
+            .map(|t| product.coeffs.get(t + n).copied().unwrap_or(Fr::ZERO))
+            .collect(),
+    )
+}
+
+/// Evaluates f on 1, q, ..., q^(n-1) with the chirp-z transform, in O((deg f + n) log(deg f + n)):
+/// with jk = (j+k)(j+k-1)/2 - j(j-1)/2 - k(k-1)/2, f(q^k) = q^(-k(k-1)/2) sum_j f_j q^(-j(j-1)/2) q^((j+k)(j+k-1)/2)
+pub fn evaluate_polynomial_geometric(f: &DensePolynomial<Fr>, q: Fr, n: usize) -> Vec<Fr> {
+    if f.is_zero() {
+        return vec![Fr::ZERO; n];
+    }
+    let N = f.coeffs.len();
+
+    // q^(m(m-1)/2) for m < N + n - 1 and their inverses
+    let mut chirp = Vec::with_capacity(N + n);
+    let (mut chirp_m, mut q_m) = (Fr::ONE, Fr::ONE);
+    for _ in 0..N + n - 1 {
+        chirp.push(chirp_m);
+        chirp_m *= q_m;
+        q_m *= q;
+    }
+    let mut chirp_inverse = chirp[..N.max(n)].to_vec();
+    batch_inversion(&mut chirp_inverse);
+
+    // a reversed convolved with the chirp gives sum_j a_j chirp_(j+k) at N-1+k
+    let a_reversed = DensePolynomial::from_coefficients_vec(
+        (0..N)
+            .rev()
+            .map(|j| f.coeffs[j] * chirp_inverse[j])
+            .collect(),
+    );
+    let product = &a_reversed * &DensePolynomial::from_coefficients_vec(chirp);
+    (0..n)
+        .map(|k| product.coeffs.get(N - 1 + k).copied().unwrap_or(Fr::ZERO) * chirp_inverse[k])
+        .collect()
+}
+
+/// Computes the quotient of f by g with a Newton iteration on the inverse of g reversed, in O(n log n)
+pub fn divide_polynomials(f: &DensePolynomial<Fr>, g: &DensePolynomial<Fr>) -> DensePolynomial<Fr> {
+    assert!(!g.is_zero(), "division by the zero polynomial");
+    if f.is_zero() || f.degree() < g.degree() {
+        return DensePolynomial::zero();
+    }
+    // long division is faster by a divisor of small degree
+    if g.degree() < 64 {
+        let (q, _) = DenseOrSparsePolynomial::divide_with_q_and_r(&f.into(), &g.into()).unwrap();
+        return q;
+    }
+    let k = f.degree() - g.degree() + 1;
+    let truncate = |p: DensePolynomial<Fr>, l: usize| {
+        DensePolynomial::from_coefficients_vec(p.coeffs.into_iter().take(l).collect())
+    };
+
+    // h = 1 / rev(g) mod x^k, doubling the precision with h <- h * (2 - rev(g) * h)
+    let g_reversed: Vec<Fr> = g.coeffs.iter().rev().copied().collect();
+    let mut h = DensePolynomial::from_coefficients_vec(vec![g_reversed[0].inverse().unwrap()]);
+    let mut l = 1;
+    while l < k {
+        l = (2 * l).min(k);
+        let g_l = DensePolynomial::from_coefficients_slice(&g_reversed[..l.min(g_reversed.len())]);
+        let error = truncate(&g_l * &h, l);
+        let correction = &DensePolynomial::from_coefficients_vec(vec![Fr::from(2)]) - &error;
+        h = truncate(&h * &correction, l);
+    }
+
+    // rev(q) = rev(f) / rev(g) mod x^k
+    let f_reversed =
+        DensePolynomial::from_coefficients_vec(f.coeffs.iter().rev().take(k).copied().collect());
+    let mut q_reversed = truncate(&f_reversed * &h, k).coeffs;
+    q_reversed.resize(k, Fr::ZERO);
+    DensePolynomial::from_coefficients_vec(q_reversed.into_iter().rev().collect())
+}
+
 /// Raises a polynomial to a power
 pub fn pow(base: &DensePolynomial<Fr>, exp: u64) -> DensePolynomial<Fr> {
     let mut result = DensePolynomial::<Fr> {
@@ -83,6 +214,21 @@ pub fn compose_polynomials(
 ) -> DensePolynomial<Fr> {
     let f_coeffs = f.coeffs();
     let n = f_coeffs.len();
+
+    // f(c*x) has the coefficients of f scaled by the powers of c
+    if let [zero, c] = g.coeffs() {
+        if zero.is_zero() {
+            let powers = std::iter::successors(Some(Fr::ONE), |power| Some(*power * c));
+            return DensePolynomial {
+                coeffs: f_coeffs
+                    .iter()
+                    .zip(powers)
+                    .map(|(a, power)| *a * power)
+                    .collect(),
+            };
+        }
+    }
+
     let first_term = DensePolynomial::<Fr> {
         coeffs: vec![f_coeffs[0]],
     };
@@ -1,4 +1,6 @@
-use crate::common::protocols::{PrescribedPermutationCheckProof, TSZeroTestProof, ZeroTestProof};
+use crate::common::protocols::{
+    PrescribedPermutationCheckProof, Selectors, TQZeroTestProof, ZeroTestProof,
+};
 use ark_bls12_381::{Fr, G1Projective as G1};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
@@ -7,33 +9,36 @@ use std::str::FromStr;
 #[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proof {
     pub pub_inputs: Vec<Fr>,
-    pub output: Fr,
+    pub outputs: Vec<Fr>,
     pub com_T: G1,
     pub proof_T_minus_v_zero: ZeroTestProof,
-    pub proof_T_S_zero: TSZeroTestProof,
+    pub proof_T_Q_zero: TQZeroTestProof,
     pub proof_T_W_prescribed_permutation: PrescribedPermutationCheckProof,
-    pub proof_last_gate_KZG: G1,
+    pub proof_T_minus_o_zero: ZeroTestProof,
 }
 
+// com_q, T(r), its proof, T(w*r), its proof, T(w^2*r), its proof, Q(r), their proofs, q(r), its proof
+type TQZeroTestProofJson = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    Selectors<String>,
+    Selectors<String>,
+    String,
+    String,
+);
+
 #[derive(Serialize, Deserialize)]
 pub struct ProofJson {
     pub pub_inputs: Vec<String>,
-    pub output: String,
+    pub outputs: Vec<String>,
     pub com_T: String,
     pub proof_T_minus_v_zero: (String, String, String, String, String),
-    pub proof_T_S_zero: (
-        String,
-        String,
-        String,
-        String,
-        String,
-        String,
-        String,
-        String,
-        String,
-        String,
-        String,
-    ),
+    pub proof_T_Q_zero: TQZeroTestProofJson,
     pub proof_T_W_prescribed_permutation: (
         String,
         String,
@@ -52,7 +57,7 @@ pub struct ProofJson {
         String,
         String,
     ),
-    pub proof_last_gate_KZG: String,
+    pub proof_T_minus_o_zero: (String, String, String, String, String),
 }
 
 impl From<&Proof> for ProofJson {
@@ -60,7 +65,7 @@ impl From<&Proof> for ProofJson {
         let mut buf = Vec::new();
 
         let pub_inputs = proof.pub_inputs.iter().map(|fr| fr.to_string()).collect();
-        let output = proof.output.to_string();
+        let outputs = proof.outputs.iter().map(|fr| fr.to_string()).collect();
 
         proof.com_T.serialize_compressed(&mut buf).unwrap();
         let com_T = hex::encode(&buf);
@@ -91,51 +96,49 @@ impl From<&Proof> for ProofJson {
         buf.clear();
 
         proof
-            .proof_T_S_zero
+            .proof_T_Q_zero
             .com_q
             .serialize_compressed(&mut buf)
             .unwrap();
-        let proof_T_S_zero_com_q = hex::encode(&buf);
+        let proof_T_Q_zero_com_q = hex::encode(&buf);
         buf.clear();
 
         proof
-            .proof_T_S_zero
+            .proof_T_Q_zero
             .proof_T_r
             .serialize_compressed(&mut buf)
             .unwrap();
-        let proof_T_S_zero_proof_T_r = hex::encode(&buf);
+        let proof_T_Q_zero_proof_T_r = hex::encode(&buf);
         buf.clear();
 
         proof
-            .proof_T_S_zero
+            .proof_T_Q_zero
             .proof_T_w_r
             .serialize_compressed(&mut buf)
             .unwrap();
-        let proof_T_S_zero_proof_T_w_r = hex::encode(&buf);
+        let proof_T_Q_zero_proof_T_w_r = hex::encode(&buf);
         buf.clear();
 
         proof
-            .proof_T_S_zero
+            .proof_T_Q_zero
             .proof_T_w2_r
             .serialize_compressed(&mut buf)
             .unwrap();
-        let proof_T_S_zero_proof_T_w2_r = hex::encode(&buf);
+        let proof_T_Q_zero_proof_T_w2_r = hex::encode(&buf);
         buf.clear();
 
-        proof
-            .proof_T_S_zero
-            .proof_S_r
-            .serialize_compressed(&mut buf)
-            .unwrap();
-        let proof_T_S_zero_proof_S_r = hex::encode(&buf);
-        buf.clear();
+        let proof_T_Q_zero_proof_Q_r = proof.proof_T_Q_zero.proof_Q_r.map(|proof_selector_r| {
+            let mut buf = Vec::new();
+            proof_selector_r.serialize_compressed(&mut buf).unwrap();
+            hex::encode(&buf)
+        });
 
         proof
-            .proof_T_S_zero
+            .proof_T_Q_zero
             .proof_q_r
             .serialize_compressed(&mut buf)
             .unwrap();
-        let proof_T_S_zero_proof_q_r = hex::encode(&buf);
+        let proof_T_Q_zero_proof_q_r = hex::encode(&buf);
         buf.clear();
 
         proof
@@ -211,15 +214,32 @@ impl From<&Proof> for ProofJson {
         buf.clear();
 
         proof
-            .proof_last_gate_KZG
+            .proof_T_minus_o_zero
+            .com_q
             .serialize_compressed(&mut buf)
             .unwrap();
-        let proof_last_gate_KZG = hex::encode(&buf);
+        let proof_T_minus_o_zero_com_q = hex::encode(&buf);
+        buf.clear();
+
+        proof
+            .proof_T_minus_o_zero
+            .proof_f_r
+            .serialize_compressed(&mut buf)
+            .unwrap();
+        let proof_T_minus_o_zero_proof_f = hex::encode(&buf);
+        buf.clear();
+
+        proof
+            .proof_T_minus_o_zero
+            .proof_q_r
+            .serialize_compressed(&mut buf)
+            .unwrap();
+        let proof_T_minus_o_zero_proof_q = hex::encode(&buf);
         buf.clear();
 
         ProofJson {
             pub_inputs,
-            output,
+            outputs,
             com_T,
             proof_T_minus_v_zero: (
                 proof_T_minus_v_zero_com_q,
@@ -228,18 +248,21 @@ impl From<&Proof> for ProofJson {
                 proof.proof_T_minus_v_zero.q_r.to_string(),
                 proof_T_minus_v_zero_proof_q,
             ),
-            proof_T_S_zero: (
-                proof_T_S_zero_com_q,
-                proof.proof_T_S_zero.T_r.to_string(),
-                proof_T_S_zero_proof_T_r,
-                proof.proof_T_S_zero.T_w_r.to_string(),
-                proof_T_S_zero_proof_T_w_r,
-                proof.proof_T_S_zero.T_w2_r.to_string(),
-                proof_T_S_zero_proof_T_w2_r,
-                proof.proof_T_S_zero.S_r.to_string(),
-                proof_T_S_zero_proof_S_r,
-                proof.proof_T_S_zero.q_r.to_string(),
-                proof_T_S_zero_proof_q_r,
+            proof_T_Q_zero: (
+                proof_T_Q_zero_com_q,
+                proof.proof_T_Q_zero.T_r.to_string(),
+                proof_T_Q_zero_proof_T_r,
+                proof.proof_T_Q_zero.T_w_r.to_string(),
+                proof_T_Q_zero_proof_T_w_r,
+                proof.proof_T_Q_zero.T_w2_r.to_string(),
+                proof_T_Q_zero_proof_T_w2_r,
+                proof
+                    .proof_T_Q_zero
+                    .Q_r
+                    .map(|selector_r| selector_r.to_string()),
+                proof_T_Q_zero_proof_Q_r,
+                proof.proof_T_Q_zero.q_r.to_string(),
+                proof_T_Q_zero_proof_q_r,
             ),
             proof_T_W_prescribed_permutation: (
                 proof_T_W_prescribed_permutation_com_t,
@@ -262,7 +285,13 @@ impl From<&Proof> for ProofJson {
                 proof.proof_T_W_prescribed_permutation.W_w_rp.to_string(),
                 proof_T_W_prescribed_permutation_proof_W_w_rp,
             ),
-            proof_last_gate_KZG,
+            proof_T_minus_o_zero: (
+                proof_T_minus_o_zero_com_q,
+                proof.proof_T_minus_o_zero.f_r.to_string(),
+                proof_T_minus_o_zero_proof_f,
+                proof.proof_T_minus_o_zero.q_r.to_string(),
+                proof_T_minus_o_zero_proof_q,
+            ),
         }
     }
 }
@@ -282,22 +311,27 @@ impl From<&ProofJson> for Proof {
         let proof_T_minus_v_zero_proof_q_bytes = hex::decode(&json.proof_T_minus_v_zero.4)
             .expect("Invalid hex in proof_T_minus_v_zero.4");
 
-        let proof_T_S_zero_com_q_bytes = hex::decode(&json.proof_T_S_zero.0).expect("Invalid hex");
-        let proof_T_S_zero_T_r = Fr::from_str(&json.proof_T_S_zero.1).expect("Invalid Fr");
-        let proof_T_S_zero_proof_T_r_bytes =
-            hex::decode(&json.proof_T_S_zero.2).expect("Invalid hex");
-        let proof_T_S_zero_T_w_r = Fr::from_str(&json.proof_T_S_zero.3).expect("Invalid Fr");
-        let proof_T_S_zero_proof_T_w_r_bytes =
-            hex::decode(&json.proof_T_S_zero.4).expect("Invalid hex");
-        let proof_T_S_zero_T_w2_r = Fr::from_str(&json.proof_T_S_zero.5).expect("Invalid Fr");
-        let proof_T_S_zero_proof_T_w2_r_bytes =
-            hex::decode(&json.proof_T_S_zero.6).expect("Invalid hex");
-        let proof_T_S_zero_S_r = Fr::from_str(&json.proof_T_S_zero.7).expect("Invalid Fr");
-        let proof_T_S_zero_proof_S_r_bytes =
-            hex::decode(&json.proof_T_S_zero.8).expect("Invalid hex");
-        let proof_T_S_zero_q_r = Fr::from_str(&json.proof_T_S_zero.9).expect("Invalid Fr");
-        let proof_T_S_zero_proof_q_r_bytes =
-            hex::decode(&json.proof_T_S_zero.10).expect("Invalid hex");
+        let proof_T_Q_zero_com_q_bytes = hex::decode(&json.proof_T_Q_zero.0).expect("Invalid hex");
+        let proof_T_Q_zero_T_r = Fr::from_str(&json.proof_T_Q_zero.1).expect("Invalid Fr");
+        let proof_T_Q_zero_proof_T_r_bytes =
+            hex::decode(&json.proof_T_Q_zero.2).expect("Invalid hex");
+        let proof_T_Q_zero_T_w_r = Fr::from_str(&json.proof_T_Q_zero.3).expect("Invalid Fr");
+        let proof_T_Q_zero_proof_T_w_r_bytes =
+            hex::decode(&json.proof_T_Q_zero.4).expect("Invalid hex");
+        let proof_T_Q_zero_T_w2_r = Fr::from_str(&json.proof_T_Q_zero.5).expect("Invalid Fr");
+        let proof_T_Q_zero_proof_T_w2_r_bytes =
+            hex::decode(&json.proof_T_Q_zero.6).expect("Invalid hex");
+        let proof_T_Q_zero_Q_r = json
+            .proof_T_Q_zero
+            .7
+            .map(|selector_r| Fr::from_str(selector_r).expect("Invalid Fr"));
+        let proof_T_Q_zero_proof_Q_r = json.proof_T_Q_zero.8.map(|proof_selector_r| {
+            let bytes = hex::decode(proof_selector_r).expect("Invalid hex");
+            G1::deserialize_compressed(&*bytes).unwrap()
+        });
+        let proof_T_Q_zero_q_r = Fr::from_str(&json.proof_T_Q_zero.9).expect("Invalid Fr");
+        let proof_T_Q_zero_proof_q_r_bytes =
+            hex::decode(&json.proof_T_Q_zero.10).expect("Invalid hex");
 
         let proof_T_W_prescribed_permutation_com_t_bytes =
             hex::decode(&json.proof_T_W_prescribed_permutation.0).expect("Invalid hex");
@@ -332,9 +366,16 @@ impl From<&ProofJson> for Proof {
         let proof_T_W_prescribed_permutation_proof_W_w_rp_bytes =
             hex::decode(&json.proof_T_W_prescribed_permutation.15).expect("Invalid hex");
 
-        let proof_last_gate_KZG_bytes =
-            hex::decode(&json.proof_last_gate_KZG).expect("Invalid hex");
-        let proof_last_gate_KZG = G1::deserialize_compressed(&*proof_last_gate_KZG_bytes).unwrap();
+        let proof_T_minus_o_zero_com_q_bytes = hex::decode(&json.proof_T_minus_o_zero.0)
+            .expect("Invalid hex in proof_T_minus_o_zero.0");
+        let proof_T_minus_o_zero_f_r =
+            Fr::from_str(&json.proof_T_minus_o_zero.1).expect("Invalid proof_T_minus_o_zero_f_r");
+        let proof_T_minus_o_zero_proof_f_bytes = hex::decode(&json.proof_T_minus_o_zero.2)
+            .expect("Invalid hex in proof_T_minus_o_zero.2");
+        let proof_T_minus_o_zero_q_r =
+            Fr::from_str(&json.proof_T_minus_o_zero.3).expect("Invalid proof_T_minus_o_zero_q_r");
+        let proof_T_minus_o_zero_proof_q_bytes = hex::decode(&json.proof_T_minus_o_zero.4)
+            .expect("Invalid hex in proof_T_minus_o_zero.4");
 
         let com_T = G1::deserialize_compressed(&*com_T_bytes).expect("Failed to deserialize com_T");
         let proof_T_minus_v_zero_com_q =
@@ -347,18 +388,18 @@ impl From<&ProofJson> for Proof {
             G1::deserialize_compressed(&*proof_T_minus_v_zero_proof_q_bytes)
                 .expect("Failed to deserialize proof_T_minus_v_zero_proof_q");
 
-        let proof_T_S_zero = TSZeroTestProof {
-            com_q: G1::deserialize_compressed(&*proof_T_S_zero_com_q_bytes).unwrap(),
-            T_r: proof_T_S_zero_T_r,
-            proof_T_r: G1::deserialize_compressed(&*proof_T_S_zero_proof_T_r_bytes).unwrap(),
-            T_w_r: proof_T_S_zero_T_w_r,
-            proof_T_w_r: G1::deserialize_compressed(&*proof_T_S_zero_proof_T_w_r_bytes).unwrap(),
-            T_w2_r: proof_T_S_zero_T_w2_r,
-            proof_T_w2_r: G1::deserialize_compressed(&*proof_T_S_zero_proof_T_w2_r_bytes).unwrap(),
-            S_r: proof_T_S_zero_S_r,
-            proof_S_r: G1::deserialize_compressed(&*proof_T_S_zero_proof_S_r_bytes).unwrap(),
-            q_r: proof_T_S_zero_q_r,
-            proof_q_r: G1::deserialize_compressed(&*proof_T_S_zero_proof_q_r_bytes).unwrap(),
+        let proof_T_Q_zero = TQZeroTestProof {
+            com_q: G1::deserialize_compressed(&*proof_T_Q_zero_com_q_bytes).unwrap(),
+            T_r: proof_T_Q_zero_T_r,
+            proof_T_r: G1::deserialize_compressed(&*proof_T_Q_zero_proof_T_r_bytes).unwrap(),
+            T_w_r: proof_T_Q_zero_T_w_r,
+            proof_T_w_r: G1::deserialize_compressed(&*proof_T_Q_zero_proof_T_w_r_bytes).unwrap(),
+            T_w2_r: proof_T_Q_zero_T_w2_r,
+            proof_T_w2_r: G1::deserialize_compressed(&*proof_T_Q_zero_proof_T_w2_r_bytes).unwrap(),
+            Q_r: proof_T_Q_zero_Q_r,
+            proof_Q_r: proof_T_Q_zero_proof_Q_r,
+            q_r: proof_T_Q_zero_q_r,
+            proof_q_r: G1::deserialize_compressed(&*proof_T_Q_zero_proof_q_r_bytes).unwrap(),
         };
 
         let proof_T_W_prescribed_permutation = PrescribedPermutationCheckProof {
@@ -408,11 +449,15 @@ impl From<&ProofJson> for Proof {
             .iter()
             .map(|s| Fr::from_str(s).expect("Invalid Fr in pub_inputs"))
             .collect();
-        let output = Fr::from_str(&json.output).expect("Invalid Fr in output");
+        let outputs = json
+            .outputs
+            .iter()
+            .map(|s| Fr::from_str(s).expect("Invalid Fr in outputs"))
+            .collect();
 
         Proof {
             pub_inputs,
-            output,
+            outputs,
             com_T,
             proof_T_minus_v_zero: ZeroTestProof {
                 com_q: proof_T_minus_v_zero_com_q,
@@ -421,9 +466,18 @@ impl From<&ProofJson> for Proof {
                 q_r: proof_T_minus_v_zero_q_r,
                 proof_q_r: proof_T_minus_v_zero_proof_q,
             },
-            proof_T_S_zero,
+            proof_T_Q_zero,
             proof_T_W_prescribed_permutation,
-            proof_last_gate_KZG,
+            proof_T_minus_o_zero: ZeroTestProof {
+                com_q: G1::deserialize_compressed(&*proof_T_minus_o_zero_com_q_bytes)
+                    .expect("Failed to deserialize proof_T_minus_o_zero_com_q"),
+                f_r: proof_T_minus_o_zero_f_r,
+                proof_f_r: G1::deserialize_compressed(&*proof_T_minus_o_zero_proof_f_bytes)
+                    .expect("Failed to deserialize proof_T_minus_o_zero_proof_f"),
+                q_r: proof_T_minus_o_zero_q_r,
+                proof_q_r: G1::deserialize_compressed(&*proof_T_minus_o_zero_proof_q_bytes)
+                    .expect("Failed to deserialize proof_T_minus_o_zero_proof_q"),
+            },
         }
     }
 }
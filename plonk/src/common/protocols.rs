@@ -1,16 +1,22 @@
 use ark_bls12_381::{Fr, G1Projective as G1};
-use ark_ff::{AdditiveGroup, Field, UniformRand};
+use ark_ff::{batch_inversion, AdditiveGroup, Field, UniformRand};
 use ark_poly::{
-    univariate::{DenseOrSparsePolynomial, DensePolynomial},
+    univariate::{DenseOrSparsePolynomial, DensePolynomial, SparsePolynomial},
     Polynomial,
 };
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use serde::{Deserialize, Serialize};
 
-use crate::common::polynomials::{compose_polynomials, interpolate_polynomial};
+use crate::common::polynomials::{
+    compose_polynomials, divide_polynomials, evaluate_polynomial_geometric, interpolate_polynomial,
+};
 
 use super::{
     kzg::{kzg_commit, kzg_evaluate, kzg_verify, GlobalParameters},
-    utils::{construct_vanishing_polynomial, construct_vanishing_polynomial_from_roots},
+    utils::{construct_vanishing_polynomial_from_roots, evaluate_vanishing_polynomial_from_roots},
 };
 
 // Struct for equality proof
@@ -106,9 +112,87 @@ pub struct PrescribedPermutationCheckProof {
     pub proof_W_w_rp: G1,
 }
 
-// Struct for T_S zero test
+// Selectors of the gate constraint qL*a + qR*b + qO*c + qM*a*b + qC = 0 on inputs a,b and output c.
+// P is Fr for the selectors of a gate, a polynomial for the selector polynomials, G1 for their commitments.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Selectors<P> {
+    pub qL: P,
+    pub qR: P,
+    pub qO: P,
+    pub qM: P,
+    pub qC: P,
+}
+
+impl<P> Selectors<P> {
+    pub fn as_array(&self) -> [&P; 5] {
+        [&self.qL, &self.qR, &self.qO, &self.qM, &self.qC]
+    }
+
+    pub fn map<Q>(&self, mut f: impl FnMut(&P) -> Q) -> Selectors<Q> {
+        Selectors {
+            qL: f(&self.qL),
+            qR: f(&self.qR),
+            qO: f(&self.qO),
+            qM: f(&self.qM),
+            qC: f(&self.qC),
+        }
+    }
+}
+
+impl Selectors<Fr> {
+    // Evaluate qL*a + qR*b + qO*c + qM*a*b + qC, which is zero when the gate is satisfied
+    pub fn evaluate_gate(&self, a: Fr, b: Fr, c: Fr) -> Fr {
+        self.qL * a + self.qR * b + self.qO * c + self.qM * a * b + self.qC
+    }
+}
+
+impl<P: CanonicalSerialize> CanonicalSerialize for Selectors<P> {
+    fn serialize_with_mode<Wr: Write>(
+        &self,
+        mut writer: Wr,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        for selector in self.as_array() {
+            selector.serialize_with_mode(&mut writer, compress)?;
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.as_array()
+            .iter()
+            .map(|selector| selector.serialized_size(compress))
+            .sum()
+    }
+}
+
+impl<P: Valid> Valid for Selectors<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.as_array()
+            .iter()
+            .try_for_each(|selector| selector.check())
+    }
+}
+
+impl<P: CanonicalDeserialize> CanonicalDeserialize for Selectors<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Selectors {
+            qL: P::deserialize_with_mode(&mut reader, compress, validate)?,
+            qR: P::deserialize_with_mode(&mut reader, compress, validate)?,
+            qO: P::deserialize_with_mode(&mut reader, compress, validate)?,
+            qM: P::deserialize_with_mode(&mut reader, compress, validate)?,
+            qC: P::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+// Struct for T_Q zero test
 #[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct TSZeroTestProof {
+pub struct TQZeroTestProof {
     pub com_q: G1,
     pub T_r: Fr,
     pub proof_T_r: G1,
@@ -116,8 +200,8 @@ pub struct TSZeroTestProof {
     pub proof_T_w_r: G1,
     pub T_w2_r: Fr,
     pub proof_T_w2_r: G1,
-    pub S_r: Fr,
-    pub proof_S_r: G1,
+    pub Q_r: Selectors<Fr>,
+    pub proof_Q_r: Selectors<G1>,
     pub q_r: Fr,
     pub proof_q_r: G1,
 }
@@ -155,8 +239,8 @@ pub fn verify_equality(
 
 // Computes the quotient polynomial q of f by the vanishing polynomial Z_Omega
 pub fn compute_q_zero_test(k: usize, f: &DensePolynomial<Fr>) -> DensePolynomial<Fr> {
-    // construct Z_Omega (vanishing polynomial)
-    let Z_Omega = construct_vanishing_polynomial(k);
+    // construct Z_Omega (vanishing polynomial) as a sparse polynomial, the division then takes O(deg f)
+    let Z_Omega = SparsePolynomial::from_coefficients_vec(vec![(0, -Fr::ONE), (k, Fr::ONE)]);
 
     // compute q as quotient of f by Z_Omega
     let (q, _) =
@@ -174,10 +258,7 @@ pub fn compute_q_zero_test_from_roots(
     let Z_Omega = construct_vanishing_polynomial_from_roots(roots);
 
     // compute q as quotient of f by Z_Omega
-    let (q, _) =
-        DenseOrSparsePolynomial::divide_with_q_and_r(&(f).into(), &(&Z_Omega).into()).unwrap();
-
-    q
+    divide_polynomials(f, &Z_Omega)
 }
 
 // Generates a proof that a polynomial (previously committed) is zero on subset Omega
@@ -219,14 +300,14 @@ pub fn verify_zero_test(
 // Verifies the proof that a polynomial (previously committed) is zero on given roots
 pub fn verify_zero_on_roots_test(
     gp: &GlobalParameters,
-    roots: &Vec<Fr>,
+    roots: &[Fr],
     com_f: G1,
     r: Fr,
     proof: &ZeroTestProof,
 ) -> bool {
-    let Z_Omega = construct_vanishing_polynomial_from_roots(roots);
+    let Z_Omega_r = evaluate_vanishing_polynomial_from_roots(roots, r);
 
-    (proof.f_r == proof.q_r * Z_Omega.evaluate(&r))
+    (proof.f_r == proof.q_r * Z_Omega_r)
         && kzg_verify(gp, proof.com_q, r, proof.q_r, proof.proof_q_r)
         && kzg_verify(gp, com_f, r, proof.f_r, proof.proof_f_r)
 }
@@ -566,24 +647,25 @@ pub fn compute_t_and_t1_prescribed_permutation_check(
 ) -> (DensePolynomial<Fr>, DensePolynomial<Fr>) {
     let mut rng = ark_std::test_rng();
 
-    // compute evaluations of t over Omega as product of evaluations r-sW(w^i)-f(w^i) divided by r-sw^i-g(w^i)
+    // compute evaluations of t over Omega as product of evaluations r-sW(w^i)-f(w^i) divided by r-sw^i-g(w^i),
+    // each product being the previous one times the factor of w^i
     let k = Omega.len();
-    let mut t_y_vals: Vec<Fr> = (0..k)
-        .map(|i| {
-            (0..i + 1)
-                .map(|j| {
-                    (
-                        Omega[j],
-                        f.evaluate(&Omega[j]),
-                        g.evaluate(&Omega[j]),
-                        W.evaluate(&Omega[j]),
-                    )
-                })
-                .map(|(w_j, f_eval, g_eval, W_eval)| {
-                    (r - s * W_eval - f_eval) * (r - s * w_j - g_eval).inverse().unwrap()
-                })
-                .reduce(|t_eval, eval| t_eval * eval)
-                .unwrap()
+    let [f_evals, g_evals, W_evals] =
+        [f, g, W].map(|poly| evaluate_polynomial_geometric(poly, Omega[1], k));
+    let mut denominators: Vec<Fr> = Omega
+        .iter()
+        .zip(&g_evals)
+        .map(|(w_j, g_eval)| r - s * w_j - g_eval)
+        .collect();
+    batch_inversion(&mut denominators);
+    let mut t_y_vals: Vec<Fr> = f_evals
+        .iter()
+        .zip(&W_evals)
+        .zip(&denominators)
+        .map(|((f_eval, W_eval), denominator)| (r - s * W_eval - f_eval) * denominator)
+        .scan(Fr::ONE, |t_eval, eval| {
+            *t_eval *= eval;
+            Some(*t_eval)
         })
         .collect();
     t_y_vals.extend((0..(f.degree() + 1 - k)).map(|_| Fr::rand(&mut rng)));
@@ -718,11 +800,11 @@ pub fn verify_prescribed_permutation_check(
         && kzg_verify(gp, com_W, rp * w, proof.W_w_rp, proof.proof_W_w_rp)
 }
 
-// Constructs the polynomial t1 based on polynomials T and S for T_S zero test
-pub fn compute_t1_T_S_zero_test(
+// Constructs the polynomial t1 based on polynomial T and selector polynomials Q for T_Q zero test
+pub fn compute_t1_T_Q_zero_test(
     w: Fr,
     T: &DensePolynomial<Fr>,
-    S: &DensePolynomial<Fr>,
+    Q: &Selectors<DensePolynomial<Fr>>,
 ) -> DensePolynomial<Fr> {
     // T(w*y)
     let T_w_y = compose_polynomials(
@@ -738,39 +820,32 @@ pub fn compute_t1_T_S_zero_test(
             coeffs: vec![Fr::ZERO, w * w],
         },
     );
-    // 1 - S(y)
-    let one_minus_S_y = compose_polynomials(
-        &DensePolynomial {
-            coeffs: vec![Fr::ONE, -Fr::ONE],
-        },
-        S,
-    );
-    // t1(y) = S(y)(T(y) + T(w*y)) + (1 - S(y))T(y)T(w*y) - T(w^2*y)
-    S * (T + &T_w_y) + &one_minus_S_y * T * T_w_y - &T_w2_y
+    // t1(y) = qL(y)T(y) + qR(y)T(w*y) + qO(y)T(w^2*y) + qM(y)T(y)T(w*y) + qC(y)
+    &Q.qL * T + &Q.qR * &T_w_y + &Q.qO * &T_w2_y + &Q.qM * &(T * &T_w_y) + Q.qC.clone()
 }
 
-// Generates the proof of T_S Zero Test on subset Omega_gates
-pub fn prove_T_S_zero_test(
+// Generates the proof of T_Q Zero Test on subset Omega_gates
+pub fn prove_T_Q_zero_test(
     gp: &GlobalParameters,
     w: Fr,
     q: &DensePolynomial<Fr>,
     T: &DensePolynomial<Fr>,
-    S: &DensePolynomial<Fr>,
+    Q: &Selectors<DensePolynomial<Fr>>,
     r: Fr,
-) -> TSZeroTestProof {
-    let com_q = kzg_commit(&gp, &q).unwrap();
+) -> TQZeroTestProof {
+    let com_q = kzg_commit(gp, q).unwrap();
     // compute T(r) and its proof
     let (T_r, proof_T_r) = kzg_evaluate(gp, T, r);
     // compute T(w*r) and its proof
     let (T_w_r, proof_T_w_r) = kzg_evaluate(gp, T, w * r);
     // compute T(w^2*r) and its proof
     let (T_w2_r, proof_T_w2_r) = kzg_evaluate(gp, T, w * w * r);
-    // compute S(r) and its proof
-    let (S_r, proof_S_r) = kzg_evaluate(gp, S, r);
+    // compute qL(r), qR(r), qO(r), qM(r), qC(r) and their proofs
+    let Q_r_with_proofs = Q.map(|selector| kzg_evaluate(gp, selector, r));
     // compute q(r) and its proof
     let (q_r, proof_q_r) = kzg_evaluate(gp, q, r);
 
-    TSZeroTestProof {
+    TQZeroTestProof {
         com_q,
         T_r,
         proof_T_r,
@@ -778,32 +853,40 @@ pub fn prove_T_S_zero_test(
         proof_T_w_r,
         T_w2_r,
         proof_T_w2_r,
-        S_r,
-        proof_S_r,
+        Q_r: Q_r_with_proofs.map(|(selector_r, _)| *selector_r),
+        proof_Q_r: Q_r_with_proofs.map(|(_, proof_selector_r)| *proof_selector_r),
         q_r,
         proof_q_r,
     }
 }
 
-// Verifies the proof of T_S zero test on subset Omega_gates
-pub fn verify_T_S_zero_test(
+// Verifies the proof of T_Q zero test on subset Omega_gates
+pub fn verify_T_Q_zero_test(
     gp: &GlobalParameters,
     w: Fr,
-    Omega_gates: &Vec<Fr>,
+    Omega_gates: &[Fr],
     com_T: G1,
-    com_S: G1,
+    com_Q: &Selectors<G1>,
     r: Fr,
-    proof: &TSZeroTestProof,
+    proof: &TQZeroTestProof,
 ) -> bool {
-    // construct Z_Omega_gates (vanishing polynomial) of subset Omega_gates
-    let Z_Omega_gates = construct_vanishing_polynomial_from_roots(&Omega_gates);
+    // evaluate Z_Omega_gates (vanishing polynomial) of subset Omega_gates on r
+    let Z_Omega_gates_r = evaluate_vanishing_polynomial_from_roots(Omega_gates, r);
 
-    proof.S_r * (proof.T_r + proof.T_w_r) + (Fr::ONE - proof.S_r) * proof.T_r * proof.T_w_r
-        - proof.T_w2_r
-        == proof.q_r * Z_Omega_gates.evaluate(&r)
+    proof
+        .Q_r
+        .evaluate_gate(proof.T_r, proof.T_w_r, proof.T_w2_r)
+        == proof.q_r * Z_Omega_gates_r
         && kzg_verify(gp, com_T, r, proof.T_r, proof.proof_T_r)
         && kzg_verify(gp, com_T, w * r, proof.T_w_r, proof.proof_T_w_r)
         && kzg_verify(gp, com_T, w * w * r, proof.T_w2_r, proof.proof_T_w2_r)
-        && kzg_verify(gp, com_S, r, proof.S_r, proof.proof_S_r)
+        && com_Q
+            .as_array()
+            .into_iter()
+            .zip(proof.Q_r.as_array())
+            .zip(proof.proof_Q_r.as_array())
+            .all(|((com_selector, selector_r), proof_selector_r)| {
+                kzg_verify(gp, *com_selector, r, *selector_r, *proof_selector_r)
+            })
         && kzg_verify(gp, proof.com_q, r, proof.q_r, proof.proof_q_r)
 }
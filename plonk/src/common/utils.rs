@@ -37,6 +37,35 @@ pub fn construct_Omega(k: usize) -> Vec<Fr> {
     (0..k).map(|i| omega.pow([i as u64])).collect()
 }
 
+// Find the smallest k >= n such that a subgroup Omega of order k exists, i.e. k divides r - 1
+pub fn subgroup_size_at_least(n: usize) -> usize {
+    let modulus_minus_1 = BigUint::from_bytes_le(&Fr::MODULUS.to_bytes_le()) - 1u64;
+
+    // r - 1 is divisible by 2^32, so the search stops at the next power of two at the latest
+    (n.max(2)..)
+        .find(|&k| &modulus_minus_1 % BigUint::from(k as u64) == BigUint::zero())
+        .unwrap()
+}
+
+// Parse a field element written in decimal (possibly negative) or in hexadecimal prefixed by 0x.
+// Values at or above the modulus r are rejected instead of being reduced, r + 1 would otherwise be read as 1.
+pub fn parse_field_element(s: &str) -> Option<Fr> {
+    let s = s.trim();
+    let (value, negative) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => (BigUint::parse_bytes(hex.as_bytes(), 16)?, false),
+        None => match s.strip_prefix('-') {
+            Some(decimal) => (BigUint::parse_bytes(decimal.as_bytes(), 10)?, true),
+            None => (BigUint::parse_bytes(s.as_bytes(), 10)?, false),
+        },
+    };
+    if value >= Fr::MODULUS.into() {
+        return None;
+    }
+
+    let x = Fr::from(value);
+    Some(if negative { -x } else { x })
+}
+
 // Construct Vanishing Polynomial as x^k - 1
 pub fn construct_vanishing_polynomial(k: usize) -> DensePolynomial<Fr> {
     let mut coefficients = vec![Fr::from(-1)];
@@ -47,18 +76,34 @@ pub fn construct_vanishing_polynomial(k: usize) -> DensePolynomial<Fr> {
     }
 }
 
-// Construct Vanishing Polynomial defined by its roots
+// Construct Vanishing Polynomial defined by its roots, multiplying the monomials pairwise up a product tree
 pub fn construct_vanishing_polynomial_from_roots(roots: &Vec<Fr>) -> DensePolynomial<Fr> {
-    let mut vanishing_polynomial = DensePolynomial {
-        coeffs: vec![Fr::ONE],
-    };
-    roots.iter().for_each(|root| {
-        vanishing_polynomial = &vanishing_polynomial
-            * &DensePolynomial {
-                coeffs: vec![-*root, Fr::ONE],
-            }
-    });
-    vanishing_polynomial
+    let mut layer: Vec<DensePolynomial<Fr>> = roots
+        .iter()
+        .map(|root| DensePolynomial {
+            coeffs: vec![-*root, Fr::ONE],
+        })
+        .collect();
+    if layer.is_empty() {
+        return DensePolynomial {
+            coeffs: vec![Fr::ONE],
+        };
+    }
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => left * right,
+                _ => pair[0].clone(),
+            })
+            .collect();
+    }
+    layer.pop().unwrap()
+}
+
+// Evaluate the vanishing polynomial defined by its roots on x without constructing it
+pub fn evaluate_vanishing_polynomial_from_roots(roots: &[Fr], x: Fr) -> Fr {
+    roots.iter().map(|root| x - root).product()
 }
 
 /// Derive a field element from a vector of commitments using Blake2s256
@@ -1,3 +1,4 @@
+pub mod circuit;
 pub mod common;
 pub mod prover;
 pub mod setup_global_params;
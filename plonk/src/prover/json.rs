@@ -0,0 +1,35 @@
+use ark_bls12_381::Fr;
+use serde::{Deserialize, Serialize};
+
+use crate::{circuit::CircuitError, common::utils::parse_field_element};
+
+// Values given to the prover, each field element in decimal or 0x-prefixed hexadecimal
+#[derive(Serialize, Deserialize)]
+pub struct ProverInputsJson {
+    pub pub_inputs: Vec<String>,
+    pub witnesses: Vec<String>,
+}
+
+pub struct ProverInputs {
+    pub pub_inputs: Vec<Fr>,
+    pub witnesses: Vec<Fr>,
+}
+
+impl ProverInputsJson {
+    pub fn into_prover_inputs(self) -> Result<ProverInputs, CircuitError> {
+        let parse = |values: &Vec<String>| {
+            values
+                .iter()
+                .map(|s| {
+                    parse_field_element(s)
+                        .ok_or_else(|| CircuitError::InvalidFieldElement(s.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(ProverInputs {
+            pub_inputs: parse(&self.pub_inputs)?,
+            witnesses: parse(&self.witnesses)?,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+pub mod json;
 pub mod part1;
 pub mod part2;
 pub mod part3;
@@ -9,7 +10,8 @@ use std::time::Instant;
 use ark_bls12_381::Fr;
 
 use crate::{
-    common::{proof::{Proof, ProofJson}, utils::construct_Omega},
+    circuit::Circuit,
+    common::{proof::Proof, utils::construct_Omega},
     setup_global_params::SetupGlobalParamsOutput,
     setup_proving_key::SetupProvingKeyOutput,
     setup_verification_key::SetupVerificationKeyOutput,
@@ -19,18 +21,20 @@ pub fn run(
     setup: &SetupGlobalParamsOutput,
     proving_key: &SetupProvingKeyOutput,
     verification_key: &SetupVerificationKeyOutput,
-) -> Result<(), Box<dyn std::error::Error>> {
+    circuit: &Circuit,
+    pub_inputs: &[Fr],
+    witnesses: &[Fr],
+) -> Result<Proof, Box<dyn std::error::Error>> {
     let start = Instant::now();
 
-    let pub_inputs = vec![Fr::from(5), Fr::from(6)];
-    let output = Fr::from(77);
+    let pub_inputs = pub_inputs.to_vec();
 
     // Define Omega as subgroup of size d
     let d = setup.d;
     let Omega = construct_Omega(d);
     assert_eq!(Omega.len(), d, "Omega must be of length d");
 
-    let (T, com_T) = part1::run(&setup, &Omega);
+    let (T, com_T) = part1::run(&setup, circuit, &pub_inputs, witnesses, &Omega)?;
     println!("✅ Part1 took: {:?}", start.elapsed());
 
     let start = Instant::now();
@@ -40,7 +44,7 @@ pub fn run(
 
     let start = Instant::now();
 
-    let proof_T_S_zero = part3::run(&setup, &proving_key, &verification_key, &Omega, &T, com_T);
+    let proof_T_Q_zero = part3::run(&setup, &proving_key, &verification_key, &Omega, &T, com_T);
     println!("✅ Part3 took: {:?}", start.elapsed());
 
     let start = Instant::now();
@@ -51,24 +55,18 @@ pub fn run(
 
     let start = Instant::now();
 
-    let proof_last_gate_KZG = part5::run(&setup, &Omega, &T, output);
+    let (outputs, proof_T_minus_o_zero) = part5::run(setup, verification_key, &Omega, &T, com_T);
     println!("✅ Part5 took: {:?}", start.elapsed());
 
     let proof = Proof {
         pub_inputs,
-        output,
+        outputs,
         com_T,
         proof_T_minus_v_zero,
-        proof_T_S_zero,
+        proof_T_Q_zero,
         proof_T_W_prescribed_permutation,
-        proof_last_gate_KZG,
+        proof_T_minus_o_zero,
     };
 
-    // Write Proof to a file
-    let proof_json = ProofJson::from(&proof);
-    let json_str = serde_json::to_string_pretty(&proof_json)?;
-    std::fs::write("data/proof.json", json_str)?;
-    println!("✅ Proof written to data/proof.json");
-
-    Ok(())
+    Ok(proof)
 }
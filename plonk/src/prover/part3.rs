@@ -1,11 +1,14 @@
 use ark_bls12_381::{Fr, G1Projective as G1};
-use ark_ff::{AdditiveGroup, Field};
-use ark_poly::{univariate::DensePolynomial, Polynomial};
+use ark_ff::{Field, Zero};
+use ark_poly::univariate::DensePolynomial;
 
 use crate::{
     common::{
-        polynomials::compose_polynomials,
-        protocols::{compute_q_zero_test_from_roots, prove_T_S_zero_test, TSZeroTestProof},
+        polynomials::evaluate_polynomial_geometric,
+        protocols::{
+            compute_q_zero_test_from_roots, compute_t1_T_Q_zero_test, prove_T_Q_zero_test,
+            TQZeroTestProof,
+        },
         utils::derive_challenge_from_commitments,
     },
     setup_global_params::SetupGlobalParamsOutput,
@@ -20,7 +23,7 @@ pub fn run(
     Omega: &Vec<Fr>,
     T: &DensePolynomial<Fr>,
     com_T: G1,
-) -> TSZeroTestProof {
+) -> TQZeroTestProof {
     println!("Executing part 3: proving that every gate is evaluated correctly");
 
     let number_gates = setup.number_gates;
@@ -32,44 +35,30 @@ pub fn run(
     let mut Omega_gates = vec![];
     (0..number_gates).for_each(|l| Omega_gates.push(Omega[3 * l]));
 
-    // Extract polynomial S and its commitment
-    let S = &proving_key.S;
-    let com_S = verification_key.com_S;
+    // Extract selector polynomials Q and their commitments
+    let Q = &proving_key.Q;
+    let com_Q = &verification_key.com_Q;
 
     let w = Omega[1];
 
-    // T(w*y)
-    let T_w_y = compose_polynomials(
-        &T,
-        &DensePolynomial {
-            coeffs: vec![Fr::ZERO, w],
-        },
-    );
-    // T(w^2*y)
-    let T_w2_y = compose_polynomials(
-        &T,
-        &DensePolynomial {
-            coeffs: vec![Fr::ZERO, w * w],
-        },
-    );
-    // 1 - S(y)
-    let one_minus_S = DensePolynomial {
-        coeffs: vec![Fr::ONE],
-    } - S;
-    let S_T = S * &(T + &T_w_y) + &one_minus_S * &(T * &T_w_y) - &T_w2_y;
-
-    for y in &Omega_gates {
-        assert_eq!(S_T.evaluate(&y), Fr::ZERO, "S_T should cancel on y");
-    }
+    // t1(y) = qL(y)T(y) + qR(y)T(w*y) + qO(y)T(w^2*y) + qM(y)T(y)T(w*y) + qC(y)
+    let t1 = compute_t1_T_Q_zero_test(w, T, Q);
 
-    // Compute quotient polynomial of S_T by the vanishing polynomial defined by Omega_gates as roots
-    let q = compute_q_zero_test_from_roots(&Omega_gates, &S_T);
+    // Omega_gates is made of the powers of w^3
+    let t1_evals = evaluate_polynomial_geometric(&t1, w.pow([3]), number_gates);
+    assert!(
+        t1_evals.iter().all(|t1_eval| t1_eval.is_zero()),
+        "t1 should cancel on Omega_gates"
+    );
 
-    // Derive challenge r from the commitments of T,S
-    let r = derive_challenge_from_commitments(&[com_T, com_S]);
+    // Compute quotient polynomial of t1 by the vanishing polynomial defined by Omega_gates as roots
+    let q = compute_q_zero_test_from_roots(&Omega_gates, &t1);
 
-    // Prove T_S zero test on Omega_gates
-    let proof_T_S_zero = prove_T_S_zero_test(gp, w, &q, &T, &S, r);
+    // Derive challenge r from the commitments of T,qL,qR,qO,qM,qC
+    let r = derive_challenge_from_commitments(&[
+        com_T, com_Q.qL, com_Q.qR, com_Q.qO, com_Q.qM, com_Q.qC,
+    ]);
 
-    proof_T_S_zero
+    // Prove T_Q zero test on Omega_gates
+    prove_T_Q_zero_test(gp, w, &q, T, Q, r)
 }
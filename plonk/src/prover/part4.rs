@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use ark_bls12_381::{Fr, G1Projective as G1};
-use ark_poly::{univariate::DensePolynomial, Polynomial};
+use ark_poly::univariate::DensePolynomial;
 
 use crate::{
     common::{
+        polynomials::evaluate_polynomial_geometric,
         protocols::{
             compute_q_zero_test, compute_t_and_t1_prescribed_permutation_check,
             prove_prescribed_permutation_check, PrescribedPermutationCheckProof,
@@ -33,10 +36,13 @@ pub fn run(
     let W = &proving_key.W;
     let com_W = verification_key.com_W;
 
-    for y in Omega {
+    // W maps every y of Omega to another element of Omega, where T must take the same value
+    let index: HashMap<Fr, usize> = Omega.iter().enumerate().map(|(i, y)| (*y, i)).collect();
+    let T_evals = evaluate_polynomial_geometric(T, Omega[1], d);
+    let W_evals = evaluate_polynomial_geometric(W, Omega[1], d);
+    for (T_eval, W_eval) in T_evals.iter().zip(&W_evals) {
         assert_eq!(
-            T.evaluate(&y),
-            T.evaluate(&W.evaluate(&y)),
+            *T_eval, T_evals[index[W_eval]],
             "T_W should be equal to T on Omega"
         );
     }
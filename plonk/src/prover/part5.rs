@@ -1,27 +1,56 @@
 use ark_bls12_381::{Fr, G1Projective as G1};
-use ark_poly::univariate::DensePolynomial;
+use ark_poly::{univariate::DensePolynomial, Polynomial};
 
-use crate::{common::kzg::kzg_evaluate, setup_global_params::SetupGlobalParamsOutput};
+use crate::{
+    common::{
+        kzg::kzg_commit,
+        polynomials::interpolate_polynomial,
+        protocols::{compute_q_zero_test_from_roots, prove_zero_test, ZeroTestProof},
+        utils::derive_challenge_from_commitments,
+    },
+    setup_global_params::SetupGlobalParamsOutput,
+    setup_verification_key::SetupVerificationKeyOutput,
+};
 
 pub fn run(
     setup: &SetupGlobalParamsOutput,
-    Omega: &Vec<Fr>,
+    verification_key: &SetupVerificationKeyOutput,
+    Omega: &[Fr],
     T: &DensePolynomial<Fr>,
-    output: Fr,
-) -> G1 {
-    println!("Executing part 5: proving the output of the last gate");
+    com_T: G1,
+) -> (Vec<Fr>, ZeroTestProof) {
+    println!("Executing part 5: proving the public outputs");
 
     // Extract global parameters
     let gp = &setup.gp;
 
-    // Extract number of gates
-    let number_gates = setup.number_gates;
+    // Define Omega_outputs from the slots fixed by the verification key
+    let Omega_outputs: Vec<Fr> = verification_key
+        .output_indices
+        .iter()
+        .map(|&i| Omega[i])
+        .collect();
 
-    // Call KZG eval on gp, T, Omega[3 * number_gates - 1]
-    let (v, proof_last_gate_KZG) = kzg_evaluate(&gp, T, Omega[3 * number_gates - 1]);
+    // The outputs are the evaluations of T on Omega_outputs
+    let outputs: Vec<Fr> = Omega_outputs.iter().map(|y| T.evaluate(y)).collect();
 
-    // Check that evaluation matches with output
-    assert_eq!(v, output, "Evaluation does not matche with output");
+    // o encodes all outputs: T(w^i) = output on every slot w^i of Omega_outputs
+    // Interpolate the polynomial o
+    let o = interpolate_polynomial(&Omega_outputs, &outputs);
+    let T_minus_o = T - &o;
 
-    proof_last_gate_KZG
+    // Compute commitment of o and derive commitment of T-o
+    let com_o = kzg_commit(gp, &o).unwrap();
+    let com_T_minus_o = com_T - com_o;
+
+    // Compute quotient polynomial of T-o by the vanishing polynomial defined by Omega_outputs as roots
+    let q = compute_q_zero_test_from_roots(&Omega_outputs, &T_minus_o);
+
+    // Derive challenge r from the commitment of T-o
+    let r = derive_challenge_from_commitments(&[com_T_minus_o]);
+
+    // Prove Zero Test of T-o on Omega_outputs, a single opening for all outputs
+    let proof_T_minus_o_zero = prove_zero_test(gp, &T_minus_o, &q, r);
+
+    (outputs, proof_T_minus_o_zero)
 }
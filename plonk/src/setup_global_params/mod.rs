@@ -1,5 +1,6 @@
 pub mod json;
 
+use crate::circuit::Circuit;
 use crate::common::kzg::{kzg_setup, GlobalParameters};
 use crate::setup_global_params::json::{GlobalParametersJson, SetupGlobalParamsOutputJson};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -47,18 +48,21 @@ pub fn convert_to_json_friendly_global_params(
     }
 }
 
-pub fn run() -> SetupGlobalParamsOutput {
+pub fn run(circuit: &Circuit) -> SetupGlobalParamsOutput {
     println!("Executing setup...");
     let start = Instant::now();
 
-    let number_gates = 3; // Circuit has 2 addition gates and 1 multiplication gate
-    let number_public_inputs = 2; // Circuit has 2 public inputs (x1, x2)
-    let number_witnesses = 1; // Circuit has 1 witness w
-    let d = 3 * number_gates + number_public_inputs + number_witnesses;
-    assert_eq!(d, 12, "d must be equal to 12");
+    let number_gates = circuit.number_gates();
+    let number_public_inputs = circuit.number_public_inputs;
+    let number_witnesses = circuit.number_witnesses;
+    let d = circuit.d();
+    assert!(
+        d >= 3 * number_gates + number_public_inputs + number_witnesses,
+        "d must be large enough to hold the gates, public inputs and witnesses"
+    );
 
-    // generate global parameters, the largest polynmial to be committed is of degree 21
-    let gp = kzg_setup(21);
+    // generate global parameters, the largest polynmial to be committed is of degree 2d-3
+    let gp = kzg_setup(2 * d - 3);
 
     println!("✅ Setup took: {:?}", start.elapsed());
 
@@ -4,29 +4,31 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::str::FromStr;
 
-use crate::setup_proving_key::SetupProvingKeyOutput;
+use crate::{common::protocols::Selectors, setup_proving_key::SetupProvingKeyOutput};
 use ark_poly::univariate::DensePolynomial;
 
 #[derive(Serialize, Deserialize)]
 pub struct SetupProvingKeyOutputJson {
-    pub S: Vec<String>,
+    pub Q: Selectors<Vec<String>>,
     pub W: Vec<String>,
 }
 
 impl SetupProvingKeyOutputJson {
     pub fn into_setup_output(self) -> SetupProvingKeyOutput {
-        let coeffs_S = self
-            .S
-            .iter()
-            .map(|s| Fr::from_str(s).expect("Invalid Fr in selector S"))
-            .collect();
+        let Q = self.Q.map(|selector| {
+            let coeffs = selector
+                .iter()
+                .map(|q| Fr::from_str(q).expect("Invalid Fr in selector polynomial"))
+                .collect();
+            DensePolynomial::from_coefficients_vec(coeffs)
+        });
         let coeffs_W = self
             .W
             .iter()
             .map(|w| Fr::from_str(w).expect("Invalid Fr in wiring polynomial W"))
             .collect();
         SetupProvingKeyOutput {
-            S: DensePolynomial::from_coefficients_vec(coeffs_S),
+            Q,
             W: DensePolynomial::from_coefficients_vec(coeffs_W),
         }
     }
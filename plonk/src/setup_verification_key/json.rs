@@ -2,22 +2,29 @@ use ark_bls12_381::G1Projective as G1;
 use ark_serialize::CanonicalDeserialize;
 use serde::{Deserialize, Serialize};
 
-use crate::setup_verification_key::SetupVerificationKeyOutput;
+use crate::{common::protocols::Selectors, setup_verification_key::SetupVerificationKeyOutput};
 
 #[derive(Serialize, Deserialize)]
 pub struct SetupVerificationKeyOutputJson {
-    pub com_S: String,
+    pub com_Q: Selectors<String>,
     pub com_W: String,
+    pub output_indices: Vec<usize>,
 }
 
 impl SetupVerificationKeyOutputJson {
     pub fn into_setup_output(self) -> SetupVerificationKeyOutput {
-        let com_S_bytes = hex::decode(&self.com_S).expect("Invalid hex in com_S");
-        let com_S = G1::deserialize_compressed(&*com_S_bytes).expect("Failed to deserialize com_S");
+        let com_Q = self.com_Q.map(|com_selector| {
+            let com_selector_bytes = hex::decode(com_selector).expect("Invalid hex in com_Q");
+            G1::deserialize_compressed(&*com_selector_bytes).expect("Failed to deserialize com_Q")
+        });
 
         let com_W_bytes = hex::decode(&self.com_W).expect("Invalid hex in com_W");
         let com_W = G1::deserialize_compressed(&*com_W_bytes).expect("Failed to deserialize com_W");
 
-        SetupVerificationKeyOutput { com_S, com_W }
+        SetupVerificationKeyOutput {
+            com_Q,
+            com_W,
+            output_indices: self.output_indices,
+        }
     }
 }
@@ -2,112 +2,69 @@ pub mod json;
 
 use std::time::Instant;
 
-use crate::common::kzg::kzg_commit;
-use crate::common::utils::construct_Omega;
-use crate::{
-    common::polynomials::interpolate_polynomial, setup_global_params::SetupGlobalParamsOutput,
-};
+use crate::circuit::Circuit;
+use crate::common::{kzg::kzg_commit, protocols::Selectors};
+use crate::{setup_global_params::SetupGlobalParamsOutput, setup_proving_key::compute_proving_key};
 
-use ark_bls12_381::{Fr, G1Projective as G1};
-use ark_ff::{AdditiveGroup, Field};
-use ark_poly::Polynomial;
+use ark_bls12_381::G1Projective as G1;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use json::SetupVerificationKeyOutputJson;
 
 #[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SetupVerificationKeyOutput {
-    pub com_S: G1,
+    pub com_Q: Selectors<G1>,
     pub com_W: G1,
+    // indices i of the slots w^i holding the public outputs
+    pub output_indices: Vec<usize>,
 }
 
 pub fn convert_to_json_friendly_verification_key(
     output: &SetupVerificationKeyOutput,
 ) -> SetupVerificationKeyOutputJson {
-    let mut buf_s = Vec::new();
-    output.com_S.serialize_compressed(&mut buf_s).unwrap();
-    let com_S = hex::encode(buf_s);
+    let com_Q = output.com_Q.map(|com_selector| {
+        let mut buf_q = Vec::new();
+        com_selector.serialize_compressed(&mut buf_q).unwrap();
+        hex::encode(buf_q)
+    });
 
     let mut buf_w = Vec::new();
     output.com_W.serialize_compressed(&mut buf_w).unwrap();
     let com_W = hex::encode(buf_w);
 
-    SetupVerificationKeyOutputJson { com_S, com_W }
+    SetupVerificationKeyOutputJson {
+        com_Q,
+        com_W,
+        output_indices: output.output_indices.clone(),
+    }
 }
 
-pub fn run(setup: &SetupGlobalParamsOutput) -> SetupVerificationKeyOutput {
+pub fn run(setup: &SetupGlobalParamsOutput, circuit: &Circuit) -> SetupVerificationKeyOutput {
     let start = Instant::now();
 
-    let d = setup.d;
-    let number_gates = setup.number_gates;
-
-    // Define Omega as subgroup of size d
-    let Omega = construct_Omega(d);
-    assert_eq!(Omega.len(), d, "Omega must be of length d");
-    // Define Omega_gates
-    let mut Omega_gates = vec![];
-    (0..number_gates).for_each(|l| Omega_gates.push(Omega[3 * l]));
-    assert_eq!(
-        Omega_gates.len(),
-        number_gates,
-        "Omega_gates must be of length number_gates"
-    );
-
-    let mut gates = vec![];
-
-    // S encodes gates: S(w^3*l) = gate#l
-    // S(w^0) = 1 -- addition gate
-    gates.push(Fr::ONE);
-    // S(w^3) = 1 -- addition gate
-    gates.push(Fr::ONE);
-    // S(w^6) = 0 -- multiplication gate
-    gates.push(Fr::ZERO);
-
-    // Interpolate the polynomial S
-    let S = interpolate_polynomial(&Omega_gates, &gates);
+    assert_eq!(setup.d, circuit.d(), "d must match the circuit");
     assert_eq!(
-        S.degree(),
-        number_gates - 1,
-        "S must be of degree (number_gates - 1)"
+        setup.number_gates,
+        circuit.number_gates(),
+        "number_gates must match the circuit"
     );
 
-    // Compute commitment of S
-    let com_S = kzg_commit(&setup.gp, &S).unwrap();
-
-    // W encodes wirings
-    let (mut W_x_vals, mut W_y_vals) = (vec![], vec![]);
-
-    // W(w^-2, w^1, w^3) = (w^1, w^3, w^-2)
-    W_x_vals.extend(vec![Omega[d - 2], Omega[1], Omega[3]]);
-    W_y_vals.extend(vec![Omega[1], Omega[3], Omega[d - 2]]);
+    // Derive Q and W exactly as the proving key does
+    let proving_key = compute_proving_key(circuit);
 
-    // W(w^-1, w^0) = (w^0, w^-1)
-    W_x_vals.extend(vec![Omega[d - 1], Omega[0]]);
-    W_y_vals.extend(vec![Omega[0], Omega[d - 1]]);
-
-    // W(w^2, w^6) = (w^6, w^2)
-    W_x_vals.extend(vec![Omega[2], Omega[6]]);
-    W_y_vals.extend(vec![Omega[6], Omega[2]]);
-
-    // W(w^-3, w^4) = (w^4, w^-3)
-    W_x_vals.extend(vec![Omega[d - 3], Omega[4]]);
-    W_y_vals.extend(vec![Omega[4], Omega[d - 3]]);
-
-    // W(w^5, w^7) = (w^7, w^5)
-    W_x_vals.extend(vec![Omega[5], Omega[7]]);
-    W_y_vals.extend(vec![Omega[7], Omega[5]]);
-
-    // W(w^8) = w^8
-    W_x_vals.push(Omega[8]);
-    W_y_vals.push(Omega[8]);
-
-    // Interpolate the polynomial W
-    let W = interpolate_polynomial(&W_x_vals, &W_y_vals);
-    assert_eq!(W.degree(), d - 1, "W must be of degree d-1");
-
-    // Compute commitment of W
-    let com_W = kzg_commit(&setup.gp, &W).unwrap();
+    // Compute commitments of qL, qR, qO, qM, qC and W
+    let com_Q = proving_key
+        .Q
+        .map(|selector| kzg_commit(&setup.gp, selector).unwrap());
+    let com_W = kzg_commit(&setup.gp, &proving_key.W).unwrap();
 
     println!("✅ Generating verification key took: {:?}", start.elapsed());
 
-    SetupVerificationKeyOutput { com_S, com_W }
+    // Fix the slots of T opened as public outputs
+    let output_indices = circuit.output_indices(setup.d);
+
+    SetupVerificationKeyOutput {
+        com_Q,
+        com_W,
+        output_indices,
+    }
 }
@@ -0,0 +1,33 @@
+use ark_bls12_381::Fr;
+use serde::{Deserialize, Serialize};
+
+use crate::{circuit::CircuitError, common::utils::parse_field_element};
+
+// Statement expected by the verifier, each field element in decimal or 0x-prefixed hexadecimal
+#[derive(Serialize, Deserialize)]
+pub struct StatementJson {
+    pub pub_inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+pub struct Statement {
+    pub pub_inputs: Vec<Fr>,
+    pub outputs: Vec<Fr>,
+}
+
+impl StatementJson {
+    pub fn into_statement(self) -> Result<Statement, CircuitError> {
+        let parse = |s: &String| {
+            parse_field_element(s).ok_or_else(|| CircuitError::InvalidFieldElement(s.clone()))
+        };
+
+        Ok(Statement {
+            pub_inputs: self
+                .pub_inputs
+                .iter()
+                .map(parse)
+                .collect::<Result<_, _>>()?,
+            outputs: self.outputs.iter().map(parse).collect::<Result<_, _>>()?,
+        })
+    }
+}
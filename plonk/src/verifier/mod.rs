@@ -1,3 +1,4 @@
+pub mod json;
 pub mod part1;
 pub mod part2;
 pub mod part3;
@@ -5,16 +6,23 @@ pub mod part4;
 
 use std::time::Instant;
 
+use ark_bls12_381::Fr;
+
 use crate::{
-    common::{proof::Proof, utils::construct_Omega}, setup_global_params::SetupGlobalParamsOutput,
+    common::{proof::Proof, utils::construct_Omega},
+    setup_global_params::SetupGlobalParamsOutput,
     setup_verification_key::SetupVerificationKeyOutput,
 };
 
-pub fn run(
+// Verify the proof against the statement expected by the caller: the public inputs and the outputs.
+// The public inputs and outputs carried by the proof are ignored.
+pub fn verify(
     setup: &SetupGlobalParamsOutput,
     verification_key: &SetupVerificationKeyOutput,
     proof: &Proof,
-) -> () {
+    pub_inputs: &[Fr],
+    outputs: &[Fr],
+) -> bool {
     let start = Instant::now();
 
     // Define Omega as subgroup of size d
@@ -22,21 +30,47 @@ pub fn run(
     let Omega = construct_Omega(d);
     assert_eq!(Omega.len(), d, "Omega must be of length d");
 
-    part1::run(setup, proof, &Omega);
+    let verified = part1::run(setup, pub_inputs, proof, &Omega);
     println!("✅ Part1 took: {:?}", start.elapsed());
+    if !verified {
+        return false;
+    }
 
     let start = Instant::now();
 
-    part2::run(setup, verification_key, proof, &Omega);
+    let verified = part2::run(setup, verification_key, proof, &Omega);
     println!("✅ Part2 took: {:?}", start.elapsed());
+    if !verified {
+        return false;
+    }
 
     let start = Instant::now();
 
-    part3::run(setup, verification_key, proof, &Omega);
+    let verified = part3::run(setup, verification_key, proof, &Omega);
     println!("✅ Part3 took: {:?}", start.elapsed());
+    if !verified {
+        return false;
+    }
 
     let start = Instant::now();
 
-    part4::run(setup, proof, &Omega);
+    let verified = part4::run(setup, verification_key, outputs, proof, &Omega);
     println!("✅ Part4 took: {:?}", start.elapsed());
+
+    verified
+}
+
+// Verify the proof against the public inputs and outputs it carries
+pub fn run(
+    setup: &SetupGlobalParamsOutput,
+    verification_key: &SetupVerificationKeyOutput,
+    proof: &Proof,
+) -> bool {
+    verify(
+        setup,
+        verification_key,
+        proof,
+        &proof.pub_inputs,
+        &proof.outputs,
+    )
 }
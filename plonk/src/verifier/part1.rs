@@ -1,16 +1,20 @@
 use crate::{common::proof::Proof, setup_global_params::SetupGlobalParamsOutput};
 
 use ark_bls12_381::Fr;
+use ark_ff::Zero;
 use ark_poly::Polynomial;
 
 use crate::common::{
-    kzg::kzg_commit,
-    polynomials::interpolate_polynomial,
-    protocols::verify_zero_on_roots_test,
+    kzg::kzg_commit, polynomials::interpolate_polynomial, protocols::verify_zero_on_roots_test,
     utils::derive_challenge_from_commitments,
 };
 
-pub fn run(setup: &SetupGlobalParamsOutput, proof: &Proof, Omega: &Vec<Fr>) -> () {
+pub fn run(
+    setup: &SetupGlobalParamsOutput,
+    pub_inputs: &[Fr],
+    proof: &Proof,
+    Omega: &Vec<Fr>,
+) -> bool {
     println!("Executing part 1: verifying that T encodes the correct inputs");
 
     // Extract number of public inputs
@@ -21,25 +25,25 @@ pub fn run(setup: &SetupGlobalParamsOutput, proof: &Proof, Omega: &Vec<Fr>) -> (
 
     let d = setup.d;
 
-    // Define Omega_inputs
+    if pub_inputs.len() != number_public_inputs {
+        println!(
+            "❌ Expected {} public inputs, got {}",
+            number_public_inputs,
+            pub_inputs.len()
+        );
+        return false;
+    }
+
+    // Define Omega_inputs = [w^-1, ..., w^-number_public_inputs], empty without public inputs
     let mut Omega_inputs = vec![];
     (0..number_public_inputs).for_each(|i| Omega_inputs.push(Omega[d - 1 - i]));
-    assert_eq!(
-        Omega_inputs,
-        vec![Omega[Omega.len() - 1], Omega[Omega.len() - 2]],
-        "Omega_inputs should be equal to [w^-1, w^-2]"
-    );
-
-    // v encodes all inputs: T(w^-j) = input#j
-    let mut pub_inputs = vec![];
-    (0..number_public_inputs).for_each(|i| pub_inputs.push(proof.pub_inputs[i]));
 
-    // Interpolate the polynomial v
-    let v = interpolate_polynomial(&Omega_inputs, &pub_inputs);
-    assert_eq!(
-        v.degree(),
-        number_public_inputs - 1,
-        "v must be of degree 1"
+    // v encodes all inputs expected by the verifier: T(w^-j) = input#j
+    // Interpolate the polynomial v, it is zero without public inputs
+    let v = interpolate_polynomial(&Omega_inputs, &pub_inputs.to_vec());
+    assert!(
+        v.is_zero() || v.degree() < number_public_inputs,
+        "v must be of degree at most (number_public_inputs - 1)"
     );
 
     // Compute commitment of v and derive commitment of T-v
@@ -50,15 +54,17 @@ pub fn run(setup: &SetupGlobalParamsOutput, proof: &Proof, Omega: &Vec<Fr>) -> (
     let r = derive_challenge_from_commitments(&[com_T_minus_v]);
 
     // Verify Zero Test of T-v on Omega_inputs
-    assert!(
-        verify_zero_on_roots_test(
-            &setup.gp,
-            &Omega_inputs,
-            com_T_minus_v,
-            r,
-            &proof.proof_T_minus_v_zero,
-        ),
-        "Verification of Zero Test of T-v must return true"
+    let verified = verify_zero_on_roots_test(
+        gp,
+        &Omega_inputs,
+        com_T_minus_v,
+        r,
+        &proof.proof_T_minus_v_zero,
     );
-    println!("✅ Verified Zero Test of T-v on Omega_inputs");
+    if verified {
+        println!("✅ Verified Zero Test of T-v on Omega_inputs");
+    } else {
+        println!("❌ Zero Test of T-v on Omega_inputs failed, T does not encode the public inputs");
+    }
+    verified
 }
@@ -2,9 +2,7 @@ use ark_bls12_381::Fr;
 
 use crate::{
     common::{
-        proof::Proof,
-        protocols::verify_T_S_zero_test,
-        utils::derive_challenge_from_commitments,
+        proof::Proof, protocols::verify_T_Q_zero_test, utils::derive_challenge_from_commitments,
     },
     setup_global_params::SetupGlobalParamsOutput,
     setup_verification_key::SetupVerificationKeyOutput,
@@ -14,8 +12,8 @@ pub fn run(
     setup: &SetupGlobalParamsOutput,
     verification_key: &SetupVerificationKeyOutput,
     proof: &Proof,
-    Omega: &Vec<Fr>
-) -> () {
+    Omega: &Vec<Fr>,
+) -> bool {
     println!("Executing part 2: verifying that every gate is evaluated correctly");
 
     // Extract number of gates
@@ -29,23 +27,29 @@ pub fn run(
     (0..number_gates).for_each(|l| Omega_gates.push(Omega[3 * l]));
 
     let com_T = proof.com_T;
-    let com_S = verification_key.com_S;
-
-    // Derive challenge r from the commitments of T,S
-    let r = derive_challenge_from_commitments(&[com_T, com_S]);
-
-    // Verify T_S zero test
-    assert!(
-        verify_T_S_zero_test(
-            &gp,
-            Omega[1],
-            &Omega_gates,
-            com_T,
-            com_S,
-            r,
-            &proof.proof_T_S_zero
-        ),
-        "Verification of T_S Zero Test of T and S must return true"
+    let com_Q = &verification_key.com_Q;
+
+    // Derive challenge r from the commitments of T,qL,qR,qO,qM,qC
+    let r = derive_challenge_from_commitments(&[
+        com_T, com_Q.qL, com_Q.qR, com_Q.qO, com_Q.qM, com_Q.qC,
+    ]);
+
+    // Verify T_Q zero test
+    let verified = verify_T_Q_zero_test(
+        gp,
+        Omega[1],
+        &Omega_gates,
+        com_T,
+        com_Q,
+        r,
+        &proof.proof_T_Q_zero,
     );
-    println!("✅ Verified T_S Zero Test of T and S on Omega_gates");
+    if verified {
+        println!("✅ Verified T_Q Zero Test of T and Q on Omega_gates");
+    } else {
+        println!(
+            "❌ T_Q Zero Test of T and Q on Omega_gates failed, gates are not evaluated correctly"
+        );
+    }
+    verified
 }
@@ -2,8 +2,7 @@ use ark_bls12_381::Fr;
 
 use crate::{
     common::{
-        proof::Proof,
-        protocols::verify_prescribed_permutation_check,
+        proof::Proof, protocols::verify_prescribed_permutation_check,
         utils::derive_multiple_challenges_from_commitments,
     },
     setup_global_params::SetupGlobalParamsOutput,
@@ -14,8 +13,8 @@ pub fn run(
     setup: &SetupGlobalParamsOutput,
     verification_key: &SetupVerificationKeyOutput,
     proof: &Proof,
-    Omega: &Vec<Fr>
-) -> () {
+    Omega: &Vec<Fr>,
+) -> bool {
     println!("Executing part 3: verifying that the wiring is implemented correctly");
 
     let d = setup.d;
@@ -31,20 +30,22 @@ pub fn run(
     let (r, s, rp) = (challenges[0], challenges[1], challenges[2]);
 
     // Verify Prescribed Permutation Check
-    assert!(
-        verify_prescribed_permutation_check(
-            &gp,
-            Omega[1],
-            d,
-            com_T,
-            com_T,
-            com_W,
-            r,
-            s,
-            rp,
-            &proof.proof_T_W_prescribed_permutation,
-        ),
-        "Verify must return true because W is prescribed permutation of T over Omega"
+    let verified = verify_prescribed_permutation_check(
+        gp,
+        Omega[1],
+        d,
+        com_T,
+        com_T,
+        com_W,
+        r,
+        s,
+        rp,
+        &proof.proof_T_W_prescribed_permutation,
     );
-    println!("✅ Verified T_W Prescribed Permutation Check on Omega");
+    if verified {
+        println!("✅ Verified T_W Prescribed Permutation Check on Omega");
+    } else {
+        println!("❌ T_W Prescribed Permutation Check on Omega failed, wiring is not respected");
+    }
+    verified
 }
@@ -1,31 +1,62 @@
 use ark_bls12_381::Fr;
 
 use crate::{
-    common::{kzg::kzg_verify, proof::Proof},
+    common::{
+        kzg::kzg_commit, polynomials::interpolate_polynomial, proof::Proof,
+        protocols::verify_zero_on_roots_test, utils::derive_challenge_from_commitments,
+    },
     setup_global_params::SetupGlobalParamsOutput,
+    setup_verification_key::SetupVerificationKeyOutput,
 };
 
-pub fn run(setup: &SetupGlobalParamsOutput, proof: &Proof, Omega: &Vec<Fr>) -> () {
-    println!("Executing part 4: verifying the output of the last gate");
+pub fn run(
+    setup: &SetupGlobalParamsOutput,
+    verification_key: &SetupVerificationKeyOutput,
+    outputs: &[Fr],
+    proof: &Proof,
+    Omega: &[Fr],
+) -> bool {
+    println!("Executing part 4: verifying the public outputs");
 
     // Extract global parameters
     let gp = &setup.gp;
 
-    // Extract number of gates
-    let number_gates = setup.number_gates;
-
-    let com_T = proof.com_T;
-
-    // Verify Prescribed Permutation Check
-    assert!(
-        kzg_verify(
-            &gp,
-            com_T,
-            Omega[3 * number_gates - 1],
-            proof.output,
-            proof.proof_last_gate_KZG,
-        ),
-        "Verify must return true because the output of last gate should match"
+    let output_indices = &verification_key.output_indices;
+    if outputs.len() != output_indices.len() {
+        println!(
+            "❌ Expected {} outputs, got {}",
+            output_indices.len(),
+            outputs.len()
+        );
+        return false;
+    }
+
+    // Define Omega_outputs from the slots fixed by the verification key
+    let Omega_outputs: Vec<Fr> = output_indices.iter().map(|&i| Omega[i]).collect();
+
+    // o encodes all outputs expected by the verifier
+    // Interpolate the polynomial o
+    let o = interpolate_polynomial(&Omega_outputs, &outputs.to_vec());
+
+    // Compute commitment of o and derive commitment of T-o
+    let com_o = kzg_commit(gp, &o).unwrap();
+    let com_T_minus_o = proof.com_T - com_o;
+
+    // Derive challenge r from the commitment of T-o
+    let r = derive_challenge_from_commitments(&[com_T_minus_o]);
+
+    // Verify Zero Test of T-o on Omega_outputs
+    let verified = verify_zero_on_roots_test(
+        gp,
+        &Omega_outputs,
+        com_T_minus_o,
+        r,
+        &proof.proof_T_minus_o_zero,
     );
-    println!("✅ Verified KZG proof of the output of the last gate");
+    if verified {
+        println!("✅ Verified Zero Test of T-o on Omega_outputs");
+    } else {
+        println!("❌ Zero Test of T-o on Omega_outputs failed, outputs do not match");
+    }
+    verified
 }
@@ -0,0 +1,165 @@
+use ark_bls12_381::Fr;
+use ark_ff::{BigInteger, Field, PrimeField};
+use plonk::{
+    circuit::{
+        circom::{read_wtns, R1cs, R1csConstraint},
+        CircuitError, Wire,
+    },
+    prover, setup_global_params, setup_proving_key, setup_verification_key, verifier,
+};
+
+// Circom binary file made of the given sections
+fn circom_file(magic: &[u8], sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = magic.to_vec();
+    bytes.extend(2u32.to_le_bytes());
+    bytes.extend((sections.len() as u32).to_le_bytes());
+    for (section_type, content) in sections {
+        bytes.extend(section_type.to_le_bytes());
+        bytes.extend((content.len() as u64).to_le_bytes());
+        bytes.extend(content);
+    }
+    bytes
+}
+
+fn field_header() -> Vec<u8> {
+    let mut bytes = 32u32.to_le_bytes().to_vec();
+    bytes.extend(Fr::MODULUS.to_bytes_le());
+    bytes
+}
+
+fn linear_combination(terms: &[(u32, Fr)]) -> Vec<u8> {
+    let mut bytes = (terms.len() as u32).to_le_bytes().to_vec();
+    for (wire, coefficient) in terms {
+        bytes.extend(wire.to_le_bytes());
+        bytes.extend(coefficient.into_bigint().to_bytes_le());
+    }
+    bytes
+}
+
+// template Example() { signal input x; signal input y; signal output out; signal t; t <== x * y; out <== t + x + 1; }
+// compiled with x public: wires are 1, out, x, y, t
+fn example_r1cs() -> Vec<u8> {
+    let mut header = field_header();
+    for value in [5u32, 1, 1, 1] {
+        header.extend(value.to_le_bytes());
+    }
+    header.extend(5u64.to_le_bytes());
+    header.extend(2u32.to_le_bytes());
+
+    // x * y = t, then 0 * 0 = t + x + 1 - out
+    let mut constraints = linear_combination(&[(2, Fr::ONE)]);
+    constraints.extend(linear_combination(&[(3, Fr::ONE)]));
+    constraints.extend(linear_combination(&[(4, Fr::ONE)]));
+    constraints.extend(linear_combination(&[]));
+    constraints.extend(linear_combination(&[]));
+    constraints.extend(linear_combination(&[
+        (4, Fr::ONE),
+        (2, Fr::ONE),
+        (0, Fr::ONE),
+        (1, -Fr::ONE),
+    ]));
+
+    circom_file(b"r1cs", &[(1, header), (2, constraints)])
+}
+
+fn example_wtns(values: &[u64]) -> Vec<u8> {
+    let mut header = field_header();
+    header.extend((values.len() as u32).to_le_bytes());
+    let content = values
+        .iter()
+        .flat_map(|&value| Fr::from(value).into_bigint().to_bytes_le())
+        .collect();
+
+    circom_file(b"wtns", &[(1, header), (2, content)])
+}
+
+#[test]
+fn test_circom_read_r1cs() {
+    let r1cs = R1cs::from_bytes(&example_r1cs()).unwrap();
+
+    assert_eq!(r1cs.number_wires, 5);
+    assert_eq!(r1cs.number_public_outputs, 1);
+    assert_eq!(r1cs.number_public_inputs, 1);
+    assert_eq!(r1cs.number_private_inputs, 1);
+    assert_eq!(
+        r1cs.constraints[0],
+        R1csConstraint {
+            a: vec![(2, Fr::ONE)],
+            b: vec![(3, Fr::ONE)],
+            c: vec![(4, Fr::ONE)],
+        }
+    );
+    assert_eq!(r1cs.constraints[1].c.len(), 4);
+}
+
+#[test]
+fn test_circom_read_wtns() {
+    let wtns = read_wtns(&example_wtns(&[1, 16, 3, 4, 12])).unwrap();
+    assert_eq!(wtns, [1, 16, 3, 4, 12].map(Fr::from));
+
+    let r1cs = R1cs::from_bytes(&example_r1cs()).unwrap();
+    let inputs = r1cs.prover_inputs(&wtns).unwrap();
+    assert_eq!(inputs.pub_inputs, vec![Fr::from(3)]);
+    assert_eq!(inputs.witnesses, [16, 4, 12].map(Fr::from));
+
+    assert!(matches!(
+        r1cs.prover_inputs(&wtns[..4]),
+        Err(CircuitError::WrongNumberOfValues {
+            expected: 5,
+            got: 4,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_circom_invalid_files() {
+    let mut r1cs = example_r1cs();
+    r1cs[0] = b'x';
+    assert!(matches!(
+        R1cs::from_bytes(&r1cs),
+        Err(CircuitError::InvalidCircomFile(_))
+    ));
+
+    // Prime other than the order of Fr, e.g. BN254 which is the default of circom
+    let mut r1cs = example_r1cs();
+    r1cs[32] ^= 1;
+    assert!(matches!(
+        R1cs::from_bytes(&r1cs),
+        Err(CircuitError::InvalidCircomFile(_))
+    ));
+
+    let r1cs = example_r1cs();
+    assert!(matches!(
+        R1cs::from_bytes(&r1cs[..r1cs.len() - 1]),
+        Err(CircuitError::InvalidCircomFile(_))
+    ));
+}
+
+#[test]
+fn test_circom_prove() {
+    let r1cs = R1cs::from_bytes(&example_r1cs()).unwrap();
+    let circuit = r1cs.into_circuit().unwrap();
+    assert_eq!(circuit.number_public_inputs, 1);
+    assert_eq!(circuit.number_witnesses, 3);
+    assert_eq!(circuit.outputs, vec![Wire::Witness(0)]);
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let wtns = read_wtns(&example_wtns(&[1, 16, 3, 4, 12])).unwrap();
+    let inputs = r1cs.prover_inputs(&wtns).unwrap();
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &inputs.pub_inputs,
+        &inputs.witnesses,
+    )
+    .unwrap();
+
+    assert_eq!(proof.outputs, vec![Fr::from(16)]);
+    assert!(verifier::run(&setup, &verification_key, &proof));
+}
@@ -0,0 +1,614 @@
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field};
+use ark_poly::Polynomial;
+use plonk::circuit::{
+    builder::CircuitBuilder,
+    convert_to_json_friendly_circuit,
+    dot::convert_to_dot,
+    json::{CircuitJson, GateJson},
+    CircuitError, Gate, Hint, HintOp, Wire,
+};
+use plonk::common::{protocols::Selectors, utils::construct_Omega};
+
+// Circuit computing (x1 + x2) * (x2 + 1), with the constant 1 given as witness
+fn example_circuit_json() -> CircuitJson {
+    serde_json::from_str(
+        r#"{
+            "public_inputs": ["x1", "x2"],
+            "witnesses": ["one"],
+            "gates": [
+                { "op": "add", "left": "x1", "right": "x2", "output": "a" },
+                { "op": "add", "left": "x2", "right": "one", "output": "b" },
+                { "op": "mul", "left": "a", "right": "b", "output": "out" }
+            ],
+            "outputs": ["out"]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_circuit_from_json() {
+    let circuit = example_circuit_json().into_circuit().unwrap();
+
+    assert_eq!(circuit.number_public_inputs, 2);
+    assert_eq!(circuit.number_witnesses, 1);
+    assert_eq!(circuit.number_gates(), 3);
+    assert_eq!(circuit.d(), 12, "d must be equal to 12");
+
+    assert_eq!(
+        circuit.gates[1],
+        Gate::addition(Wire::PublicInput(1), Wire::Witness(0))
+    );
+    assert_eq!(
+        circuit.gates[2],
+        Gate::multiplication(Wire::Gate(0), Wire::Gate(1))
+    );
+}
+
+#[test]
+fn test_circuit_selectors() {
+    let circuit = example_circuit_json().into_circuit().unwrap();
+
+    let addition = Selectors {
+        qL: Fr::ONE,
+        qR: Fr::ONE,
+        qO: -Fr::ONE,
+        ..Default::default()
+    };
+    let multiplication = Selectors {
+        qO: -Fr::ONE,
+        qM: Fr::ONE,
+        ..Default::default()
+    };
+    assert_eq!(
+        circuit.selectors(),
+        vec![addition.clone(), addition, multiplication],
+        "Selectors must encode a + b - c on additions and a*b - c on multiplications"
+    );
+}
+
+#[test]
+fn test_circuit_wiring_cycles() {
+    let circuit = example_circuit_json().into_circuit().unwrap();
+    let d = circuit.d();
+
+    let mut cycles = circuit.wiring_cycles(d);
+    cycles.sort();
+
+    let mut expected = vec![
+        vec![0, d - 1],    // x1
+        vec![1, 3, d - 2], // x2
+        vec![4, d - 3],    // one
+        vec![2, 6],        // a
+        vec![5, 7],        // b
+        vec![8],           // out
+    ];
+    expected.sort();
+
+    assert_eq!(cycles, expected, "Wiring cycles must match the circuit");
+}
+
+#[test]
+fn test_circuit_wiring_cycles_pad_Omega() {
+    // 2 gates + 1 public input need 7 slots, Omega is padded to 8
+    let circuit = CircuitJson {
+        public_inputs: vec!["x".to_string()],
+        witnesses: vec![],
+        gates: vec![
+            GateJson {
+                op: "mul".to_string(),
+                left: Some("x".to_string()),
+                right: Some("x".to_string()),
+                output: "x2".to_string(),
+                value: None,
+                selectors: None,
+            },
+            GateJson {
+                op: "mul".to_string(),
+                left: Some("x2".to_string()),
+                right: Some("x".to_string()),
+                output: "x3".to_string(),
+                value: None,
+                selectors: None,
+            },
+        ],
+        outputs: vec![],
+        hints: vec![],
+    }
+    .into_circuit()
+    .unwrap();
+    let d = circuit.d();
+    assert_eq!(d, 8, "d must be padded to 8");
+
+    let cycles = circuit.wiring_cycles(d);
+    assert!(
+        cycles.contains(&vec![0, 1, 4, 7]),
+        "x must be wired to its uses"
+    );
+    assert!(
+        cycles.contains(&vec![6]),
+        "Padding slot must be a fixed point"
+    );
+
+    let mut slots: Vec<usize> = cycles.concat();
+    slots.sort();
+    assert_eq!(slots, (0..d).collect::<Vec<_>>(), "Cycles must cover Omega");
+}
+
+#[test]
+fn test_circuit_json_roundtrip() {
+    let circuit = example_circuit_json().into_circuit().unwrap();
+
+    let json = convert_to_json_friendly_circuit(&circuit);
+    assert_eq!(json.public_inputs, vec!["x0", "x1"]);
+    assert_eq!(json.gates[2].output, "g2");
+    assert_eq!(json.outputs, vec!["g2"]);
+
+    assert_eq!(json.into_circuit().unwrap(), circuit);
+}
+
+#[test]
+fn test_circuit_from_json_outputs() {
+    let mut json = example_circuit_json();
+    json.outputs = vec!["a".to_string(), "x1".to_string()];
+    let circuit = json.into_circuit().unwrap();
+    let d = circuit.d();
+
+    assert_eq!(circuit.outputs, vec![Wire::Gate(0), Wire::PublicInput(0)]);
+    assert_eq!(circuit.output_indices(d), vec![2, d - 1]);
+}
+
+#[test]
+fn test_circuit_from_json_invalid_outputs() {
+    let mut json = example_circuit_json();
+    json.outputs = vec!["out".to_string(), "out".to_string()];
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::DuplicateOutput(name)) if name == "out"
+    ));
+
+    let mut json = example_circuit_json();
+    json.outputs = vec!["c".to_string()];
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::UnknownVariable(name)) if name == "c"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_unknown_variable() {
+    let mut json = example_circuit_json();
+    json.gates[2].right = Some("c".to_string());
+
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::UnknownVariable(name)) if name == "c"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_duplicate_variable() {
+    let mut json = example_circuit_json();
+    json.gates[0].output = "x2".to_string();
+
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::DuplicateVariable(name)) if name == "x2"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_unknown_gate_type() {
+    let mut json = example_circuit_json();
+    json.gates[0].op = "div".to_string();
+
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::UnknownGateType(op)) if op == "div"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_hints() {
+    // inv = 1 / x checked by x * inv - 1 = 0, and bit 0 of x given without check
+    let json: CircuitJson = serde_json::from_str(
+        r#"{
+            "public_inputs": ["x"],
+            "witnesses": ["w", "inv", "parity"],
+            "gates": [
+                { "op": "gate", "left": "x", "right": "inv", "output": "check",
+                  "selectors": { "qL": "0", "qR": "0", "qO": "0", "qM": "1", "qC": "-1" } }
+            ],
+            "hints": [
+                { "op": "inverse", "input": "x", "output": "inv" },
+                { "op": "bit", "input": "x", "output": "parity", "bit": 0 }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let circuit = json.into_circuit().unwrap();
+    assert_eq!(
+        circuit.hints,
+        vec![
+            Hint {
+                witness: 1,
+                op: HintOp::Inverse,
+                input: Wire::PublicInput(0),
+            },
+            Hint {
+                witness: 2,
+                op: HintOp::Bit(0),
+                input: Wire::PublicInput(0),
+            },
+        ]
+    );
+    assert_eq!(circuit.given_witnesses(), vec![0]);
+    assert_eq!(
+        convert_to_json_friendly_circuit(&circuit)
+            .into_circuit()
+            .unwrap(),
+        circuit
+    );
+}
+
+#[test]
+fn test_circuit_from_json_invalid_hints() {
+    let hint = |op: &str, output: &str| {
+        let mut json = example_circuit_json();
+        json.hints = serde_json::from_str(&format!(
+            r#"[{{ "op": "{}", "input": "x1", "output": "{}" }}]"#,
+            op, output
+        ))
+        .unwrap();
+        json.into_circuit()
+    };
+
+    assert!(matches!(
+        hint("log", "one"),
+        Err(CircuitError::UnknownHintType(op)) if op == "log"
+    ));
+    // the index of the bit is missing
+    assert!(matches!(
+        hint("bit", "one"),
+        Err(CircuitError::UnknownHintType(op)) if op == "bit"
+    ));
+    assert!(matches!(
+        hint("inverse", "a"),
+        Err(CircuitError::InvalidHintOutput(name)) if name == "a"
+    ));
+}
+
+// Circuit checking x*y = 2z + 1 with a custom gate, and returning z - x
+fn custom_gates_circuit_json() -> CircuitJson {
+    serde_json::from_str(
+        r#"{
+            "public_inputs": ["x", "y"],
+            "witnesses": ["z"],
+            "gates": [
+                { "op": "gate", "left": "x", "right": "y", "output": "xy_minus_1",
+                  "selectors": { "qL": "0", "qR": "0", "qO": "-1", "qM": "1", "qC": "-1" } },
+                { "op": "gate", "left": "z", "output": "two_z",
+                  "selectors": { "qL": "2", "qR": "0", "qO": "-1", "qM": "0", "qC": "0" } },
+                { "op": "gate", "left": "xy_minus_1", "right": "two_z", "output": "check",
+                  "selectors": { "qL": "1", "qR": "-1", "qO": "0", "qM": "0", "qC": "0" } },
+                { "op": "sub", "left": "z", "right": "x", "output": "out" }
+            ]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_circuit_from_json_custom_gates() {
+    let circuit = custom_gates_circuit_json().into_circuit().unwrap();
+
+    assert_eq!(circuit.gates[0].selectors.qC, -Fr::ONE);
+    assert_eq!(circuit.gates[1].right, None);
+    assert_eq!(
+        circuit.gates[3],
+        Gate::subtraction(Wire::Witness(0), Wire::PublicInput(0))
+    );
+
+    // unused input of gate#1 is not wired to any other slot
+    let cycles = circuit.wiring_cycles(circuit.d());
+    assert!(cycles.contains(&vec![4]));
+
+    // gates are written back with their named operation when possible
+    let json = convert_to_json_friendly_circuit(&circuit);
+    assert_eq!(json.gates[0].op, "gate");
+    assert_eq!(json.gates[1].right, None);
+    assert_eq!(json.gates[3].op, "sub");
+    assert_eq!(json.into_circuit().unwrap(), circuit);
+}
+
+#[test]
+fn test_circuit_from_json_missing_selectors() {
+    let mut json = custom_gates_circuit_json();
+    json.gates[1].selectors = None;
+
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::MissingSelectors(name)) if name == "two_z"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_unwired_input_with_nonzero_selector() {
+    // gate#1 has no right input, qR = 1 would read a slot that the prover sets freely
+    let mut json = custom_gates_circuit_json();
+    json.gates[1].selectors.as_mut().unwrap().qR = "1".to_string();
+
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::UnwiredInputWithNonZeroSelector { gate }) if gate == "two_z"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_unconstrained_output_read() {
+    // gate `check` has qO = 0, nothing constrains its output
+    let mut json = custom_gates_circuit_json();
+    json.gates[3].right = Some("check".to_string());
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::UnconstrainedOutputRead { gate }) if gate == "check"
+    ));
+
+    let mut json = custom_gates_circuit_json();
+    json.outputs = vec!["check".to_string()];
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::UnconstrainedOutputRead { gate }) if gate == "check"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_missing_gate_input() {
+    let mut json = example_circuit_json();
+    json.gates[2].left = None;
+
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::MissingGateInput(name)) if name == "out"
+    ));
+}
+
+#[test]
+fn test_circuit_from_json_constant() {
+    let json: CircuitJson = serde_json::from_str(
+        r#"{
+            "public_inputs": ["x"],
+            "witnesses": [],
+            "gates": [
+                { "op": "const", "value": "0x10", "output": "sixteen" },
+                { "op": "mul", "left": "x", "right": "sixteen", "output": "out" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let circuit = json.into_circuit().unwrap();
+
+    assert_eq!(circuit.gates[0], Gate::constant(Fr::from(16)));
+    assert_eq!(circuit.gates[0].selectors.qC, Fr::from(16));
+
+    let json = convert_to_json_friendly_circuit(&circuit);
+    assert_eq!(json.gates[0].op, "const");
+    assert_eq!(json.gates[0].value, Some("16".to_string()));
+    assert_eq!(json.into_circuit().unwrap(), circuit);
+}
+
+#[test]
+fn test_circuit_from_json_missing_constant_value() {
+    let json: CircuitJson = serde_json::from_str(
+        r#"{
+            "public_inputs": [],
+            "witnesses": [],
+            "gates": [{ "op": "const", "output": "one" }]
+        }"#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        json.into_circuit(),
+        Err(CircuitError::MissingConstantValue(name)) if name == "one"
+    ));
+}
+
+#[test]
+fn test_circuit_builder_constant() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let one = builder.constant(Fr::ONE);
+    let two = builder.constant(Fr::from(2));
+    assert_eq!(
+        builder.constant(Fr::ONE),
+        one,
+        "A constant must be fixed by a single gate"
+    );
+    let a = builder.add(x, one);
+    builder.mul(a, two);
+    let (circuit, proving_key) = builder.finalize().unwrap();
+
+    assert_eq!(circuit.number_gates(), 4);
+    assert_eq!(circuit.number_witnesses, 0);
+    assert_eq!(circuit.gates[1], Gate::constant(Fr::from(2)));
+
+    // qC(w^3l) fixes the output of constant gates
+    let Omega = construct_Omega(circuit.d());
+    assert_eq!(proving_key.Q.qC.evaluate(&Omega[0]), Fr::ONE);
+    assert_eq!(proving_key.Q.qC.evaluate(&Omega[3]), Fr::from(2));
+    assert_eq!(proving_key.Q.qC.evaluate(&Omega[6]), Fr::ZERO);
+}
+
+#[test]
+fn test_circuit_builder() {
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let one = builder.witness();
+    let a = builder.add(x1, x2);
+    let b = builder.add(x2, one);
+    let out = builder.mul(a, b);
+    assert_eq!(out, Wire::Gate(2));
+    builder.output(out);
+
+    assert_eq!(
+        builder.build().unwrap(),
+        example_circuit_json().into_circuit().unwrap(),
+        "Builder must produce the same circuit as the JSON description"
+    );
+}
+
+#[test]
+fn test_circuit_builder_witness_before_public_input() {
+    // Witnesses are laid out after all public inputs, whatever the allocation order
+    let mut builder = CircuitBuilder::new();
+    let w = builder.witness();
+    let x = builder.public_input();
+    builder.mul(w, x);
+    let circuit = builder.build().unwrap();
+    let d = circuit.d();
+
+    assert_eq!(circuit.wire_index(x, d), d - 1);
+    assert_eq!(circuit.wire_index(w, d), d - 2);
+}
+
+#[test]
+fn test_circuit_builder_finalize() {
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let one = builder.witness();
+    let a = builder.add(x1, x2);
+    let b = builder.add(x2, one);
+    builder.mul(a, b);
+    let (circuit, proving_key) = builder.finalize().unwrap();
+
+    let d = circuit.d();
+    let Omega = construct_Omega(d);
+    let (Q, W) = (&proving_key.Q, &proving_key.W);
+
+    // qM(w^3l) selects the gate type, qO(w^3l) selects its output
+    assert_eq!(Q.qM.evaluate(&Omega[0]), Fr::ZERO);
+    assert_eq!(Q.qM.evaluate(&Omega[3]), Fr::ZERO);
+    assert_eq!(Q.qM.evaluate(&Omega[6]), Fr::ONE);
+    assert_eq!(Q.qL.evaluate(&Omega[3]), Fr::ONE);
+    assert_eq!(Q.qO.evaluate(&Omega[6]), -Fr::ONE);
+
+    // W(w^-2, w^1, w^3) = (w^1, w^3, w^-2)
+    assert_eq!(W.evaluate(&Omega[d - 2]), Omega[1]);
+    assert_eq!(W.evaluate(&Omega[1]), Omega[3]);
+    assert_eq!(W.evaluate(&Omega[3]), Omega[d - 2]);
+    // W(w^2, w^6) = (w^6, w^2)
+    assert_eq!(W.evaluate(&Omega[2]), Omega[6]);
+    assert_eq!(W.evaluate(&Omega[6]), Omega[2]);
+    // W(w^8) = w^8
+    assert_eq!(W.evaluate(&Omega[8]), Omega[8]);
+}
+
+#[test]
+#[should_panic(expected = "is not allocated")]
+fn test_circuit_builder_unallocated_wire() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    builder.add(x, Wire::Gate(0));
+}
+
+#[test]
+fn test_circuit_builder_unwired_input_with_nonzero_selector() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let square = Selectors {
+        qM: Fr::ONE,
+        qO: -Fr::ONE,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        builder.try_gate(square.clone(), Some(x), None),
+        Err(CircuitError::UnwiredInputWithNonZeroSelector { gate }) if gate == "gate#0"
+    ));
+    assert_eq!(
+        builder.try_gate(square, Some(x), Some(x)).unwrap(),
+        Wire::Gate(0)
+    );
+}
+
+#[test]
+fn test_circuit_builder_unconstrained_output_read() {
+    // x * x = 1 has qO = 0, its output is not constrained
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let square_is_one = Selectors {
+        qM: Fr::ONE,
+        qC: -Fr::ONE,
+        ..Default::default()
+    };
+    let unconstrained = builder.gate(square_is_one.clone(), Some(x), Some(x));
+    let sum = builder.add(unconstrained, x);
+    builder.output(sum);
+    assert!(matches!(
+        builder.build(),
+        Err(CircuitError::UnconstrainedOutputRead { gate }) if gate == "gate#0"
+    ));
+
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    builder.constrain(square_is_one, Some(x), Some(x));
+    let square = builder.mul(x, x);
+    builder.output(square);
+    assert_eq!(builder.build().unwrap().number_gates(), 2);
+}
+
+#[test]
+#[should_panic(expected = "use gate for a gate with an output")]
+fn test_circuit_builder_constrain_with_output() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    builder.constrain(
+        Selectors {
+            qL: Fr::ONE,
+            qO: -Fr::ONE,
+            ..Default::default()
+        },
+        Some(x),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "is already an output")]
+fn test_circuit_builder_duplicate_output() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let y = builder.mul(x, x);
+    builder.output(y);
+    builder.output(y);
+}
+
+#[test]
+fn test_circuit_convert_to_dot() {
+    let circuit = example_circuit_json().into_circuit().unwrap();
+
+    // d = 12: x0, x1 and w0 are at w^-1, w^-2 and w^-3
+    assert_eq!(
+        convert_to_dot(&circuit),
+        r#"digraph circuit {
+    rankdir=LR;
+    x0 [shape=box, peripheries=1, label="x0\nw^-1"];
+    x1 [shape=box, peripheries=1, label="x1\nw^-2"];
+    w0 [shape=box, peripheries=1, label="w0\nw^-3"];
+    g0 [peripheries=1, label="g0: add\nw^0..w^2"];
+    g1 [peripheries=1, label="g1: add\nw^3..w^5"];
+    g2 [peripheries=2, label="g2: mul\nw^6..w^8"];
+    x0 -> g0 [label="a"];
+    x1 -> g0 [label="b"];
+    x1 -> g1 [label="a"];
+    g0 -> g2 [label="a"];
+    w0 -> g1 [label="b"];
+    g1 -> g2 [label="b"];
+}
+"#
+    );
+}
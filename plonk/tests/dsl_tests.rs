@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+use plonk::{
+    circuit::{builder::CircuitBuilder, dsl, witness, CircuitError, Gate, Wire},
+    common::protocols::Selectors,
+    prover, setup_global_params, setup_proving_key, setup_verification_key, verifier,
+};
+
+#[test]
+fn test_dsl_compile() {
+    let circuit = dsl::compile("public x1, x2; out = (x1 + x2) * (x2 + 1)").unwrap();
+
+    // The constant 1 is folded into the gate computing x2 + 1
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let a = builder.add(x1, x2);
+    let b = builder.add_constant(x2, Fr::ONE);
+    let out = builder.mul(a, b);
+    builder.output(out);
+    assert_eq!(circuit, builder.build().unwrap());
+}
+
+#[test]
+fn test_dsl_compile_statements() {
+    let circuit = dsl::compile(
+        r#"
+        # (x1 - x2) * w, and 3 - w
+        public x1, x2
+        witness w
+
+        diff = x1 - x2
+        alias = diff   # no gate for an alias
+        output alias * w, 3 - w, x1
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(circuit.number_public_inputs, 2);
+    assert_eq!(circuit.number_witnesses, 1);
+    assert_eq!(
+        circuit.gates,
+        vec![
+            Gate::subtraction(Wire::PublicInput(0), Wire::PublicInput(1)),
+            Gate::multiplication(Wire::Gate(0), Wire::Witness(0)),
+            Gate {
+                selectors: Selectors {
+                    qL: -Fr::ONE,
+                    qO: -Fr::ONE,
+                    qC: Fr::from(3),
+                    ..Default::default()
+                },
+                left: Some(Wire::Witness(0)),
+                right: None,
+            },
+        ]
+    );
+    assert_eq!(
+        circuit.outputs,
+        vec![Wire::Gate(1), Wire::Gate(2), Wire::PublicInput(0)]
+    );
+
+    let trace =
+        witness::generate_trace(&circuit, &[Fr::from(9), Fr::from(4)], &[Fr::from(2)]).unwrap();
+    assert_eq!(trace[5], Fr::from(10));
+    assert_eq!(trace[8], Fr::from(1));
+}
+
+#[test]
+fn test_dsl_compile_constant_folding() {
+    // 2 * 3 - 6 + 1 folds to 1, multiplying by 1 adds no gate
+    let circuit = dsl::compile("public x; y = (2 * 3 - 6 + 1) * x * 5 + 0").unwrap();
+
+    assert_eq!(circuit.number_gates(), 1);
+    assert_eq!(circuit.gates[0].selectors.qL, Fr::from(5));
+
+    // A constant output is computed by a const gate
+    let circuit = dsl::compile("public x; output x, -(0x2 + 1)").unwrap();
+    assert_eq!(circuit.gates, vec![Gate::constant(-Fr::from(3))]);
+}
+
+#[test]
+fn test_dsl_compile_errors() {
+    assert!(matches!(
+        dsl::compile("public x\ny = x * z"),
+        Err(CircuitError::UnknownVariable(name)) if name == "z"
+    ));
+    assert!(matches!(
+        dsl::compile("public x\nx = x + 1"),
+        Err(CircuitError::DuplicateVariable(name)) if name == "x"
+    ));
+    assert!(matches!(
+        dsl::compile("public x\ny = x + 12a"),
+        Err(CircuitError::InvalidFieldElement(number)) if number == "12a"
+    ));
+    assert!(matches!(
+        dsl::compile("public x\n\ny = (x + 1"),
+        Err(CircuitError::Syntax { line: 3, .. })
+    ));
+    assert!(matches!(
+        dsl::compile("public x\ny = x % 2"),
+        Err(CircuitError::Syntax { line: 2, .. })
+    ));
+    assert!(matches!(
+        dsl::compile("public x, output"),
+        Err(CircuitError::Syntax { line: 1, .. })
+    ));
+}
+
+#[test]
+fn test_dsl_compile_aliased_outputs() {
+    // y and z name the wire of x * x, each output gets its own wire holding its value
+    let circuit = dsl::compile("public x; y = x * x; z = y; output y, z, 4, 4").unwrap();
+    assert_eq!(circuit.outputs.len(), 4);
+    let distinct: HashSet<_> = circuit.outputs.iter().collect();
+    assert_eq!(distinct.len(), 4);
+
+    let trace = witness::generate_trace(&circuit, &[Fr::from(3)], &[]).unwrap();
+    let outputs: Vec<Fr> = circuit
+        .output_indices(circuit.d())
+        .into_iter()
+        .map(|i| trace[i])
+        .collect();
+    assert_eq!(outputs, [9, 9, 4, 4].map(Fr::from));
+}
+
+#[test]
+fn test_dsl_prove() {
+    let circuit = dsl::compile("public x1, x2\nwitness w\nout = (x1 + x2) * (x2 + 1) - w").unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[Fr::from(5), Fr::from(6)],
+        &[Fr::from(7)],
+    )
+    .unwrap();
+
+    assert_eq!(proof.outputs, vec![Fr::from(70)]);
+    assert!(verifier::run(&setup, &verification_key, &proof));
+}
+
+#[test]
+fn test_dsl_compile_division() {
+    let circuit = dsl::compile("public x, y; output x / y, x / 4, 1 / (x - y)").unwrap();
+    // x / 4 is x * 1/4, divisions by variables compute their inverse with a hint
+    assert_eq!(circuit.hints.len(), 2);
+    assert_eq!(circuit.given_witnesses(), Vec::<usize>::new());
+
+    let (x, y) = (Fr::from(6), Fr::from(4));
+    let trace = witness::generate_trace(&circuit, &[x, y], &[]).unwrap();
+    let outputs: Vec<Fr> = circuit
+        .output_indices(circuit.d())
+        .into_iter()
+        .map(|i| trace[i])
+        .collect();
+    assert_eq!(
+        outputs,
+        vec![x / y, x / Fr::from(4), (x - y).inverse().unwrap()]
+    );
+
+    assert!(matches!(
+        dsl::compile("public x; output x / (2 - 2)"),
+        Err(CircuitError::Syntax { line: 1, .. })
+    ));
+}
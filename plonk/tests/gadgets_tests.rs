@@ -0,0 +1,711 @@
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field, PrimeField};
+use num_bigint::BigUint;
+use plonk::{
+    circuit::{
+        builder::CircuitBuilder,
+        gadgets::{
+            bits::bits_le,
+            eddsa,
+            jubjub::{self, Point, PointWires},
+            merkle::{self, MerkleTree},
+            poseidon, sha256,
+        },
+        mock::check_trace,
+        witness::generate_trace,
+        Circuit, CircuitError, Wire,
+    },
+    prover, setup_global_params, setup_proving_key, setup_verification_key, verifier,
+};
+
+// Whether the trace computed from the inputs satisfies every constraint of the circuit
+fn is_satisfied(circuit: &Circuit, pub_inputs: &[Fr], witnesses: &[Fr]) -> bool {
+    let trace = generate_trace(circuit, pub_inputs, witnesses).unwrap();
+    check_trace(circuit, &trace).is_empty()
+}
+
+fn value(circuit: &Circuit, wire: Wire, pub_inputs: &[Fr], witnesses: &[Fr]) -> Fr {
+    let trace = generate_trace(circuit, pub_inputs, witnesses).unwrap();
+    trace[circuit.wire_index(wire, circuit.d())]
+}
+
+#[test]
+fn test_enforce_boolean() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    builder.enforce_boolean(x);
+    let circuit = builder.build().unwrap();
+
+    assert!(is_satisfied(&circuit, &[Fr::from(0)], &[]));
+    assert!(is_satisfied(&circuit, &[Fr::from(1)], &[]));
+    assert!(!is_satisfied(&circuit, &[Fr::from(2)], &[]));
+    assert!(!is_satisfied(&circuit, &[-Fr::ONE], &[]));
+}
+
+#[test]
+fn test_bits_le() {
+    assert_eq!(bits_le(Fr::from(13), 5), [1, 0, 1, 1, 0].map(Fr::from));
+    assert_eq!(bits_le(Fr::from(13), 2), [1, 0].map(Fr::from));
+}
+
+#[test]
+fn test_decompose() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let bits = builder.decompose(x, 4);
+    let circuit = builder.build().unwrap();
+    assert_eq!(bits, (0..4).map(Wire::Witness).collect::<Vec<_>>());
+
+    // The bits are computed by hints
+    let x = Fr::from(13);
+    assert!(is_satisfied(&circuit, &[x], &[]));
+    let values: Vec<Fr> = bits
+        .iter()
+        .map(|&bit| value(&circuit, bit, &[x], &[]))
+        .collect();
+    assert_eq!(values, bits_le(x, 4));
+
+    // 16 does not fit in 4 bits
+    assert!(!is_satisfied(&circuit, &[Fr::from(16)], &[]));
+
+    // Without hints, a dishonest prover gives bits of another value, or values that are not bits
+    let mut circuit = circuit;
+    circuit.hints.clear();
+    assert!(is_satisfied(&circuit, &[x], &bits_le(x, 4)));
+    assert!(!is_satisfied(&circuit, &[x], &bits_le(Fr::from(12), 4)));
+    assert!(!is_satisfied(
+        &circuit,
+        &[x],
+        &[3, 1, 0, 1].map(Fr::from) // 3 + 2 + 8 = 13
+    ));
+}
+
+#[test]
+fn test_recompose() {
+    let mut builder = CircuitBuilder::new();
+    let bits: Vec<Wire> = (0..5).map(|_| builder.witness()).collect();
+    let x = builder.recompose(&bits);
+    let circuit = builder.build().unwrap();
+
+    let witnesses = [1, 0, 1, 1, 0].map(Fr::from);
+    assert_eq!(value(&circuit, x, &[], &witnesses), Fr::from(13));
+    assert_eq!(circuit.number_gates(), 4);
+}
+
+#[test]
+fn test_range_check_prove() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    builder.range_check(x, 8);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let x = Fr::from(200);
+    let proof = prover::run(&setup, &proving_key, &verification_key, &circuit, &[x], &[]).unwrap();
+    assert!(verifier::run(&setup, &verification_key, &proof));
+    assert!(
+        !verifier::verify(&setup, &verification_key, &proof, &[Fr::from(300)], &[]),
+        "Verify must return false because the proof is for x = 200"
+    );
+
+    // 300 does not fit in 8 bits, its 8 bits do not satisfy the circuit
+    let x = Fr::from(300);
+    let result = prover::run(&setup, &proving_key, &verification_key, &circuit, &[x], &[]);
+    assert!(matches!(
+        result.map_err(|error| error.downcast::<CircuitError>().map(|error| *error)),
+        Err(Ok(CircuitError::UnsatisfiedConstraints(_)))
+    ));
+}
+
+#[test]
+fn test_comparison_prove() {
+    // Outputs a < b and a <= b for 4-bit values
+    let mut builder = CircuitBuilder::new();
+    let a = builder.public_input();
+    let b = builder.public_input();
+    builder.range_check(a, 4);
+    builder.range_check(b, 4);
+    let less_than = builder.less_than(a, b, 4);
+    let less_or_equal = builder.less_or_equal(a, b, 4);
+    builder.output(less_than);
+    builder.output(less_or_equal);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    for (a, b, expected) in [(3, 5, [1, 1]), (5, 5, [0, 1]), (15, 0, [0, 0])] {
+        let (a, b) = (Fr::from(a), Fr::from(b));
+        let proof = prover::run(
+            &setup,
+            &proving_key,
+            &verification_key,
+            &circuit,
+            &[a, b],
+            &[],
+        )
+        .unwrap();
+
+        let expected = expected.map(Fr::from);
+        assert_eq!(proof.outputs, expected);
+        assert!(verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &[a, b],
+            &expected
+        ));
+        assert!(
+            !verifier::verify(
+                &setup,
+                &verification_key,
+                &proof,
+                &[a, b],
+                &[Fr::ONE - expected[0], expected[1]]
+            ),
+            "Verify must return false because the comparison is claimed the other way round"
+        );
+    }
+}
+
+fn from_hex(s: &str) -> Fr {
+    Fr::from_be_bytes_mod_order(&hex::decode(s).unwrap())
+}
+
+#[test]
+fn test_poseidon_permute() {
+    // Test vector of the reference implementation poseidonperm_x5_255_3
+    let output = poseidon::permute([0, 1, 2].map(Fr::from));
+    assert_eq!(
+        output,
+        [
+            "28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a",
+            "51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4",
+            "3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a",
+        ]
+        .map(from_hex)
+    );
+}
+
+#[test]
+fn test_poseidon_permutation_gadget() {
+    let mut builder = CircuitBuilder::new();
+    let state = [(); 3].map(|_| builder.public_input());
+    let output = builder.poseidon_permutation(state);
+    let circuit = builder.build().unwrap();
+    assert_eq!(circuit.number_gates(), 633);
+
+    let input = [Fr::from(7), Fr::from(11), -Fr::ONE];
+    let expected = poseidon::permute(input);
+    for (wire, expected) in output.into_iter().zip(expected) {
+        assert_eq!(value(&circuit, wire, &input, &[]), expected);
+    }
+}
+
+#[test]
+fn test_poseidon_hash_gadget() {
+    // Knowledge of a preimage (x, y) of a public hash
+    let mut builder = CircuitBuilder::new();
+    let expected = builder.public_input();
+    let x = builder.witness();
+    let y = builder.witness();
+    let hash = builder.poseidon_hash(&[x, y]);
+    builder.enforce_equal(hash, expected);
+    let circuit = builder.build().unwrap();
+
+    let preimage = [Fr::from(42), Fr::from(1337)];
+    let expected = poseidon::hash(&preimage);
+    assert_ne!(expected, poseidon::hash(&[Fr::from(42)]));
+    assert_ne!(expected, poseidon::hash(&[Fr::from(1337), Fr::from(42)]));
+    assert!(is_satisfied(&circuit, &[expected], &preimage));
+    assert!(!is_satisfied(
+        &circuit,
+        &[expected],
+        &[Fr::from(42), Fr::from(1338)]
+    ));
+}
+
+#[test]
+fn test_merkle_tree() {
+    let leaves: Vec<Fr> = (10..18).map(Fr::from).collect();
+    let tree = MerkleTree::new(leaves.clone());
+    assert_eq!(tree.depth(), 3);
+
+    let left = merkle::compress(
+        merkle::compress(leaves[0], leaves[1]),
+        merkle::compress(leaves[2], leaves[3]),
+    );
+    let right = merkle::compress(
+        merkle::compress(leaves[4], leaves[5]),
+        merkle::compress(leaves[6], leaves[7]),
+    );
+    assert_eq!(tree.root(), merkle::compress(left, right));
+
+    for (index, &leaf) in leaves.iter().enumerate() {
+        let path = tree.path(index);
+        assert!(merkle::verify_path(tree.root(), leaf, index, &path));
+        assert!(!merkle::verify_path(tree.root(), leaf, index ^ 1, &path));
+        assert!(!merkle::verify_path(tree.root(), Fr::from(9), index, &path));
+    }
+}
+
+#[test]
+fn test_merkle_membership_circuit() {
+    // The leaf, its index and its path are witnesses, the root is public
+    let mut builder = CircuitBuilder::new();
+    let root = builder.public_input();
+    let leaf = builder.witness();
+    let index_bits = [builder.witness(), builder.witness()];
+    let path = [builder.witness(), builder.witness()];
+    builder.verify_merkle_path(root, leaf, &index_bits, &path);
+    let circuit = builder.build().unwrap();
+
+    let tree = MerkleTree::new((1..5).map(Fr::from).collect());
+    let index = 2;
+    let witnesses = [
+        vec![tree.leaf(index)],
+        bits_le(Fr::from(index as u64), 2),
+        tree.path(index),
+    ]
+    .concat();
+    assert!(is_satisfied(&circuit, &[tree.root()], &witnesses));
+
+    // Another tree has another root
+    let other = MerkleTree::new((2..6).map(Fr::from).collect());
+    assert!(!is_satisfied(&circuit, &[other.root()], &witnesses));
+
+    // The leaf 5 is not in the tree, no path authenticates it
+    let mut witnesses = witnesses;
+    witnesses[0] = Fr::from(5);
+    assert!(!is_satisfied(&circuit, &[tree.root()], &witnesses));
+}
+
+#[test]
+fn test_merkle_membership_prove() {
+    // Membership in a tree of depth 1, whose single Poseidon hash keeps the circuit small enough to prove
+    let mut builder = CircuitBuilder::new();
+    let root = builder.public_input();
+    let leaf = builder.witness();
+    let index_bits = [builder.witness()];
+    let path = [builder.witness()];
+    builder.verify_merkle_path(root, leaf, &index_bits, &path);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let tree = MerkleTree::new(vec![Fr::from(7), Fr::from(8)]);
+    let witnesses = [tree.leaf(1), Fr::ONE, tree.path(1)[0]];
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[tree.root()],
+        &witnesses,
+    )
+    .unwrap();
+    assert!(verifier::verify(
+        &setup,
+        &verification_key,
+        &proof,
+        &[tree.root()],
+        &[]
+    ));
+
+    let other = MerkleTree::new(vec![Fr::from(7), Fr::from(9)]);
+    assert!(
+        !verifier::verify(&setup, &verification_key, &proof, &[other.root()], &[]),
+        "Verify must return false because the proof is for another root"
+    );
+}
+
+#[test]
+fn test_boolean_logic() {
+    let mut builder = CircuitBuilder::new();
+    let a = builder.public_input();
+    let b = builder.public_input();
+    let and = builder.and(a, b);
+    let or = builder.or(a, b);
+    let xor = builder.xor(a, b);
+    let not = builder.not(a);
+    let circuit = builder.build().unwrap();
+
+    for (x, y) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+        let inputs = [x, y].map(Fr::from);
+        let value = |wire| value(&circuit, wire, &inputs, &[]);
+        assert_eq!(value(and), Fr::from(x & y));
+        assert_eq!(value(or), Fr::from(x | y));
+        assert_eq!(value(xor), Fr::from(x ^ y));
+        assert_eq!(value(not), Fr::from(1 - x));
+    }
+}
+
+#[test]
+fn test_is_zero() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let out = builder.is_zero(x);
+    let circuit = builder.build().unwrap();
+
+    for (x, expected) in [
+        (Fr::ZERO, Fr::ONE),
+        (Fr::from(5), Fr::ZERO),
+        (-Fr::ONE, Fr::ZERO),
+    ] {
+        assert!(is_satisfied(&circuit, &[x], &[]));
+        assert_eq!(value(&circuit, out, &[x], &[]), expected);
+    }
+
+    // Without hint, a wrong inverse of a non-zero value does not satisfy the circuit, whatever the output
+    let mut circuit = circuit;
+    circuit.hints.clear();
+    let x = Fr::from(5);
+    assert!(is_satisfied(&circuit, &[x], &[x.inverse().unwrap()]));
+    assert!(!is_satisfied(&circuit, &[x], &[Fr::ZERO]));
+    assert!(!is_satisfied(&circuit, &[x], &[Fr::from(2)]));
+}
+
+#[test]
+fn test_logic_prove() {
+    // Outputs (a and b) or not c, and x if a xor b else y, and whether x = y
+    let mut builder = CircuitBuilder::new();
+    let [a, b, c, x, y] = [(); 5].map(|_| builder.public_input());
+    for bit in [a, b, c] {
+        builder.enforce_boolean(bit);
+    }
+    let and = builder.and(a, b);
+    let not = builder.not(c);
+    let or = builder.or(and, not);
+    let xor = builder.xor(a, b);
+    let selected = builder.select(xor, x, y);
+    let difference = builder.sub(x, y);
+    let equal = builder.is_zero(difference);
+    builder.output(or);
+    builder.output(selected);
+    builder.output(equal);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    for (inputs, expected) in [
+        ([1, 0, 1, 7, 9], [0, 7, 0]),
+        ([1, 1, 1, 7, 7], [1, 7, 1]),
+        ([0, 0, 0, 3, 4], [1, 4, 0]),
+    ] {
+        let inputs = inputs.map(Fr::from);
+        let proof = prover::run(
+            &setup,
+            &proving_key,
+            &verification_key,
+            &circuit,
+            &inputs,
+            &[],
+        )
+        .unwrap();
+        let expected = expected.map(Fr::from);
+        assert_eq!(proof.outputs, expected);
+        assert!(verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &inputs,
+            &expected
+        ));
+    }
+
+    // 2 is not a boolean
+    let inputs = [2, 0, 1, 7, 9].map(Fr::from);
+    assert!(prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &inputs,
+        &[],
+    )
+    .is_err());
+}
+
+// Digest of message computed by the SHA-256 circuit, checking that the trace satisfies the circuit
+fn sha256_circuit(message: &[u8]) -> Vec<u8> {
+    let mut builder = CircuitBuilder::new();
+    let bits: Vec<Wire> = (0..8 * message.len()).map(|_| builder.witness()).collect();
+    for &bit in &bits {
+        builder.enforce_boolean(bit);
+    }
+    for word in builder.sha256(&bits) {
+        let packed = builder.pack_word(&word);
+        builder.output(packed);
+    }
+    let circuit = builder.build().unwrap();
+
+    let witnesses = sha256::message_bits(message);
+    let trace = generate_trace(&circuit, &[], &witnesses).unwrap();
+    assert!(check_trace(&circuit, &trace).is_empty());
+    let words: Vec<Fr> = circuit
+        .output_indices(circuit.d())
+        .into_iter()
+        .map(|i| trace[i])
+        .collect();
+    sha256::digest_bytes(&words)
+}
+
+fn from_hex_bytes(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_sha256_vectors() {
+    // FIPS 180-2 examples, the last one takes two blocks
+    let vectors = [
+        (
+            "",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            "abc",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ),
+    ];
+    for (message, digest) in vectors {
+        assert_eq!(sha256_circuit(message.as_bytes()), from_hex_bytes(digest));
+    }
+}
+
+#[test]
+fn test_sha256_compression() {
+    // Single block of "abc" padded by hand
+    let mut block = [0u32; 16];
+    block[0] = 0x61626380;
+    block[15] = 24;
+    let iv = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut builder = CircuitBuilder::new();
+    let state = iv.map(|h| builder.constant_word(h));
+    let block = block.map(|w| builder.constant_word(w));
+    let output = builder.sha256_compression(&state, &block);
+    let packed = builder.pack_word(&output[0]);
+    let circuit = builder.build().unwrap();
+
+    assert!(is_satisfied(&circuit, &[], &[]));
+    assert_eq!(value(&circuit, packed, &[], &[]), Fr::from(0xba7816bfu32));
+}
+
+#[test]
+fn test_sha256_invalid_bits() {
+    // A message bit that is not boolean must be rejected even if the rest of the trace is consistent
+    let mut builder = CircuitBuilder::new();
+    let bits: Vec<Wire> = (0..8).map(|_| builder.witness()).collect();
+    for &bit in &bits {
+        builder.enforce_boolean(bit);
+    }
+    builder.sha256(&bits);
+    let circuit = builder.build().unwrap();
+
+    let mut witnesses = sha256::message_bits(b"a");
+    assert!(is_satisfied(&circuit, &[], &witnesses));
+    witnesses[7] = Fr::from(2);
+    assert!(!is_satisfied(&circuit, &[], &witnesses));
+}
+
+#[test]
+fn test_jubjub_points() {
+    let generator = Point::GENERATOR;
+    assert!(generator.is_on_curve());
+    assert_eq!(jubjub::D * Fr::from(10241), -Fr::from(10240));
+    assert_eq!(generator.scalar_mul(&jubjub::order()), Point::IDENTITY);
+    assert_eq!(generator + Point::IDENTITY, generator);
+    assert_eq!(generator + -generator, Point::IDENTITY);
+
+    let (a, b) = (BigUint::from(1234u32), BigUint::from(98765u32));
+    let (a_g, b_g) = (generator.scalar_mul(&a), generator.scalar_mul(&b));
+    assert!(a_g.is_on_curve());
+    assert_eq!(a_g + b_g, generator.scalar_mul(&(&a + &b)));
+    assert_eq!(a_g.double(), generator.scalar_mul(&(&a * 2u8)));
+}
+
+#[test]
+fn test_jubjub_gadgets() {
+    let mut builder = CircuitBuilder::new();
+    let p = PointWires {
+        x: builder.public_input(),
+        y: builder.public_input(),
+    };
+    let q = PointWires {
+        x: builder.public_input(),
+        y: builder.public_input(),
+    };
+    let scalar = builder.public_input();
+    builder.enforce_on_curve(p);
+    let sum = builder.jubjub_add(p, q);
+    let double = builder.jubjub_double(p);
+    let bits = builder.decompose(scalar, 7);
+    let product = builder.jubjub_scalar_mul(p, &bits);
+    let fixed_base_product = builder.jubjub_fixed_base_mul(Point::GENERATOR, &bits);
+    let circuit = builder.build().unwrap();
+
+    let (p, q) = (
+        Point::GENERATOR.scalar_mul(&BigUint::from(5u8)),
+        Point::GENERATOR.scalar_mul(&BigUint::from(11u8)),
+    );
+    let pub_inputs = [p.x, p.y, q.x, q.y, Fr::from(101)];
+    assert!(is_satisfied(&circuit, &pub_inputs, &[]));
+    let point = |wires: PointWires| Point {
+        x: value(&circuit, wires.x, &pub_inputs, &[]),
+        y: value(&circuit, wires.y, &pub_inputs, &[]),
+    };
+    assert_eq!(point(sum), p + q);
+    assert_eq!(point(double), p.double());
+    assert_eq!(point(product), p.scalar_mul(&BigUint::from(101u8)));
+    assert_eq!(
+        point(fixed_base_product),
+        Point::GENERATOR.scalar_mul(&BigUint::from(101u8))
+    );
+
+    // (1, 1) is not on the curve
+    let pub_inputs = [Fr::ONE, Fr::ONE, q.x, q.y, Fr::from(101)];
+    assert!(!is_satisfied(&circuit, &pub_inputs, &[]));
+}
+
+#[test]
+fn test_eddsa() {
+    let secret_key = BigUint::from(123456789u32);
+    let public_key = eddsa::public_key(&secret_key);
+    let message = Fr::from(42);
+    let signature = eddsa::sign(&secret_key, message);
+
+    assert!(eddsa::verify(public_key, message, &signature));
+    assert!(!eddsa::verify(public_key, message + Fr::ONE, &signature));
+    assert!(!eddsa::verify(
+        eddsa::public_key(&(secret_key + 1u8)),
+        message,
+        &signature
+    ));
+    let mut malleated = signature.clone();
+    malleated.s += jubjub::order();
+    assert!(!eddsa::verify(public_key, message, &malleated));
+}
+
+#[test]
+fn test_eddsa_circuit() {
+    // The public key is public, the message and the signature are witnesses
+    let mut builder = CircuitBuilder::new();
+    let public_key = PointWires {
+        x: builder.public_input(),
+        y: builder.public_input(),
+    };
+    let message = builder.witness();
+    let r = PointWires {
+        x: builder.witness(),
+        y: builder.witness(),
+    };
+    let s = builder.witness();
+    builder.verify_eddsa(public_key, message, r, s);
+    let circuit = builder.build().unwrap();
+
+    let secret_key = BigUint::from(987654321u32);
+    let key = eddsa::public_key(&secret_key);
+    let signature = eddsa::sign(&secret_key, Fr::from(7));
+    let witnesses = [
+        Fr::from(7),
+        signature.r.x,
+        signature.r.y,
+        Fr::from(signature.s.clone()),
+    ];
+
+    assert!(is_satisfied(&circuit, &[key.x, key.y], &witnesses));
+
+    // A signature of another message or under another key does not satisfy the circuit
+    let mut other_message = witnesses;
+    other_message[0] = Fr::from(8);
+    assert!(!is_satisfied(&circuit, &[key.x, key.y], &other_message));
+    let other_key = eddsa::public_key(&(&secret_key + 1u8));
+    assert!(!is_satisfied(
+        &circuit,
+        &[other_key.x, other_key.y],
+        &witnesses
+    ));
+
+    // s + order is another representative of s, it must be rejected to prevent malleability
+    let mut malleated = witnesses;
+    malleated[3] += Fr::from(jubjub::order());
+    assert!(!is_satisfied(&circuit, &[key.x, key.y], &malleated));
+}
+
+#[test]
+fn test_eddsa_prove() {
+    // The public key is public, the message and the signature are witnesses
+    let mut builder = CircuitBuilder::new();
+    let public_key = PointWires {
+        x: builder.public_input(),
+        y: builder.public_input(),
+    };
+    let message = builder.witness();
+    let r = PointWires {
+        x: builder.witness(),
+        y: builder.witness(),
+    };
+    let s = builder.witness();
+    builder.verify_eddsa(public_key, message, r, s);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let secret_key = BigUint::from(987654321u32);
+    let key = eddsa::public_key(&secret_key);
+    let signature = eddsa::sign(&secret_key, Fr::from(7));
+    let witnesses = [
+        Fr::from(7),
+        signature.r.x,
+        signature.r.y,
+        Fr::from(signature.s),
+    ];
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[key.x, key.y],
+        &witnesses,
+    )
+    .unwrap();
+    assert!(verifier::verify(
+        &setup,
+        &verification_key,
+        &proof,
+        &[key.x, key.y],
+        &[]
+    ));
+
+    let other_key = eddsa::public_key(&(&secret_key + 1u8));
+    assert!(
+        !verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &[other_key.x, other_key.y],
+            &[]
+        ),
+        "Verify must return false because the signature is from another key"
+    );
+}
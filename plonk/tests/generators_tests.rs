@@ -0,0 +1,86 @@
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+use plonk::{
+    circuit::{
+        generators::{self, GeneratedCircuit},
+        mock::check_trace,
+    },
+    prover, verifier,
+};
+
+// Prove the generated circuit with its own keys and verify the proof against its statement
+fn prove_and_verify(generated: &GeneratedCircuit) -> bool {
+    let (setup, proving_key, verification_key) = generated.setup();
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &generated.circuit,
+        &generated.pub_inputs,
+        &generated.witnesses,
+    )
+    .unwrap();
+    verifier::verify(
+        &setup,
+        &verification_key,
+        &proof,
+        &generated.pub_inputs,
+        &generated.outputs().unwrap(),
+    )
+}
+
+#[test]
+fn test_generated_sizes_and_outputs() {
+    let fibonacci = generators::fibonacci(10);
+    assert_eq!(fibonacci.circuit.number_gates(), 8);
+    assert_eq!(fibonacci.outputs().unwrap(), vec![Fr::from(55)]);
+
+    let squaring = generators::repeated_squaring(5);
+    assert_eq!(squaring.circuit.number_gates(), 5);
+    assert_eq!(squaring.outputs().unwrap(), vec![Fr::from(3).pow([32])]);
+
+    // 13 = 0b1101: 3 squarings and 2 multiplications
+    let exponentiation = generators::exponentiation(13);
+    assert_eq!(exponentiation.circuit.number_gates(), 5);
+    assert_eq!(
+        exponentiation.outputs().unwrap(),
+        vec![Fr::from(3u64.pow(13))]
+    );
+
+    // 1*4 + 2*3 + 3*2 + 4*1
+    let dot_product = generators::dot_product(4);
+    assert_eq!(dot_product.circuit.number_gates(), 7);
+    assert_eq!(dot_product.circuit.number_public_inputs, 4);
+    assert_eq!(dot_product.circuit.number_witnesses, 4);
+    assert_eq!(dot_product.outputs().unwrap(), vec![Fr::from(20)]);
+}
+
+#[test]
+fn test_generated_traces_are_valid() {
+    for n in [3, 17, 100] {
+        for generated in [
+            generators::fibonacci(n),
+            generators::repeated_squaring(n),
+            generators::exponentiation(n as u64),
+            generators::dot_product(n),
+        ] {
+            let trace = generated.trace().unwrap();
+            assert_eq!(trace.len(), generated.circuit.d());
+            assert!(check_trace(&generated.circuit, &trace).is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_generated_circuits_prove() {
+    assert!(prove_and_verify(&generators::fibonacci(20)));
+    assert!(prove_and_verify(&generators::repeated_squaring(20)));
+    assert!(prove_and_verify(&generators::exponentiation(1000)));
+    assert!(prove_and_verify(&generators::dot_product(20)));
+}
+
+#[test]
+#[should_panic(expected = "at least 3 terms")]
+fn test_fibonacci_too_short() {
+    generators::fibonacci(2);
+}
@@ -68,6 +68,24 @@ fn test_kzg_commit_success() {
     );
 }
 
+#[test]
+fn test_kzg_commit_matches_sum_of_tau_powers() {
+    // The multi-scalar multiplication must match the sum of f_i * tau^i * g1, with fewer
+    // coefficients than powers of tau
+    let mut rng = ark_std::test_rng();
+    let gp = kzg_setup(40);
+    for degree in [0, 7, 40] {
+        let f = random_polynomial(&mut rng, degree);
+        let expected: G1 = f
+            .coeffs
+            .iter()
+            .zip(&gp.tau_powers_g1)
+            .map(|(f_i, tau_i_g1)| *tau_i_g1 * f_i)
+            .sum();
+        assert_eq!(kzg_commit(&gp, &f).unwrap(), expected);
+    }
+}
+
 #[test]
 fn test_kzg_commit_fail() {
     let mut rng = ark_std::test_rng();
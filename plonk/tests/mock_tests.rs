@@ -0,0 +1,115 @@
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+use plonk::{
+    circuit::{
+        builder::CircuitBuilder,
+        mock::{check_trace, ConstraintFailure},
+        witness::generate_trace,
+        Circuit, CircuitError,
+    },
+    common::protocols::Selectors,
+    prover, setup_global_params, setup_proving_key, setup_verification_key,
+};
+
+// Circuit computing (x1 + x2) * (x2 + 1), with the constant 1 given as witness
+fn example_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let one = builder.witness();
+    let a = builder.add(x1, x2);
+    let b = builder.add(x2, one);
+    let out = builder.mul(a, b);
+    builder.output(out);
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_check_trace() {
+    let circuit = example_circuit();
+    let trace = generate_trace(&circuit, &[Fr::from(5), Fr::from(6)], &[Fr::from(1)]).unwrap();
+
+    assert!(check_trace(&circuit, &trace).is_empty());
+}
+
+#[test]
+fn test_check_trace_reports_gate_and_wiring() {
+    let circuit = example_circuit();
+    let mut trace = generate_trace(&circuit, &[Fr::from(5), Fr::from(6)], &[Fr::from(1)]).unwrap();
+
+    // Left input of the multiplication no longer copies the output 11 of gate#0
+    trace[6] = Fr::from(12);
+    let failures = check_trace(&circuit, &trace);
+
+    assert_eq!(
+        failures,
+        vec![
+            ConstraintFailure::Gate {
+                l: 2,
+                a: Fr::from(12),
+                b: Fr::from(7),
+                c: Fr::from(77),
+                value: Fr::from(7),
+            },
+            ConstraintFailure::CopyConstraint {
+                cycle: vec![2, 6],
+                values: vec![Fr::from(11), Fr::from(12)],
+            },
+        ]
+    );
+    assert_eq!(
+        failures[0].to_string(),
+        "gate#2 at w^6..w^8 is not satisfied: qL*a + qR*b + qO*c + qM*a*b + qC = 7 with a = 12, b = 7, c = 77"
+    );
+    assert_eq!(
+        failures[1].to_string(),
+        "wiring cycle [2, 6] carries different values [11, 12]"
+    );
+}
+
+#[test]
+fn test_prover_reports_unsatisfied_gate() {
+    // x1 * x2 = 12 is checked by a gate with qO = 0
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    builder.gate(
+        Selectors {
+            qM: Fr::ONE,
+            qC: -Fr::from(12),
+            ..Default::default()
+        },
+        Some(x1),
+        Some(x2),
+    );
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let Err(error) = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[Fr::from(3), Fr::from(5)],
+        &[],
+    ) else {
+        panic!("Prover must fail because x1 * x2 != 12");
+    };
+
+    match error.downcast_ref::<CircuitError>() {
+        Some(CircuitError::UnsatisfiedConstraints(failures)) => assert_eq!(
+            failures,
+            &vec![ConstraintFailure::Gate {
+                l: 0,
+                a: Fr::from(3),
+                b: Fr::from(5),
+                c: Fr::from(0),
+                value: Fr::from(3),
+            }]
+        ),
+        _ => panic!("Prover must report the unsatisfied gate, got {}", error),
+    }
+}
@@ -0,0 +1,245 @@
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field};
+use plonk::{
+    circuit::{
+        builder::CircuitBuilder,
+        optimizer::{
+            eliminate_common_subexpressions, fold_constants, merge_linear_gates, optimize,
+            remove_dead_gates, PassReport,
+        },
+        witness::generate_trace,
+        Circuit, Gate, Wire,
+    },
+    common::protocols::Selectors,
+    prover, setup_global_params, setup_proving_key, setup_verification_key, verifier,
+};
+
+// Values of the outputs of the circuit computed from the trace
+fn outputs(circuit: &Circuit, pub_inputs: &[u64], witnesses: &[u64]) -> Vec<Fr> {
+    let pub_inputs: Vec<Fr> = pub_inputs.iter().map(|&x| Fr::from(x)).collect();
+    let witnesses: Vec<Fr> = witnesses.iter().map(|&w| Fr::from(w)).collect();
+    let trace = generate_trace(circuit, &pub_inputs, &witnesses).unwrap();
+    circuit
+        .output_indices(circuit.d())
+        .into_iter()
+        .map(|i| trace[i])
+        .collect()
+}
+
+#[test]
+fn test_fold_constants() {
+    // (x * (1 + 2)) * 1 and 1 + 2
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let one = builder.constant(Fr::ONE);
+    let two = builder.constant(Fr::from(2));
+    let three = builder.add(one, two);
+    let a = builder.mul(x, three);
+    let out = builder.mul(a, one);
+    builder.output(out);
+    builder.output(three);
+    let circuit = builder.build().unwrap();
+
+    let folded = fold_constants(&circuit).unwrap();
+
+    assert_eq!(
+        folded.gates,
+        vec![
+            Gate {
+                selectors: Selectors {
+                    qL: Fr::from(3),
+                    qO: -Fr::ONE,
+                    ..Default::default()
+                },
+                left: Some(Wire::PublicInput(0)),
+                right: None,
+            },
+            Gate::constant(Fr::from(3)),
+        ]
+    );
+    assert_eq!(outputs(&folded, &[5], &[]), outputs(&circuit, &[5], &[]));
+}
+
+#[test]
+fn test_eliminate_common_subexpressions() {
+    // (x1 + x2) * (x2 + x1), with x2 + x1 written with qO = -2
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let a = builder.add(x1, x2);
+    let b = builder.gate(
+        Selectors {
+            qL: Fr::from(2),
+            qR: Fr::from(2),
+            qO: -Fr::from(2),
+            ..Default::default()
+        },
+        Some(x2),
+        Some(x1),
+    );
+    let out = builder.mul(a, b);
+    builder.output(out);
+    let circuit = builder.build().unwrap();
+
+    let optimized = eliminate_common_subexpressions(&circuit).unwrap();
+
+    assert_eq!(
+        optimized.gates,
+        vec![
+            Gate::addition(x1, x2),
+            Gate::multiplication(Wire::Gate(0), Wire::Gate(0)),
+        ]
+    );
+    assert_eq!(outputs(&optimized, &[3, 4], &[]), vec![Fr::from(49)]);
+}
+
+#[test]
+fn test_eliminate_common_subexpressions_duplicate_outputs() {
+    // Both outputs compute x1 * x2, each one keeps its own wire
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let a = builder.mul(x1, x2);
+    let b = builder.mul(x2, x1);
+    builder.output(a);
+    builder.output(b);
+    let circuit = builder.build().unwrap();
+
+    let optimized = eliminate_common_subexpressions(&circuit).unwrap();
+
+    assert_eq!(optimized.outputs.len(), 2);
+    assert_ne!(optimized.outputs[0], optimized.outputs[1]);
+    assert_eq!(
+        outputs(&optimized, &[3, 4], &[]),
+        vec![Fr::from(12), Fr::from(12)]
+    );
+}
+
+#[test]
+fn test_remove_dead_gates() {
+    // x1 + x2 is never read, x1 * x2 = 12 is a constraint and is kept
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    builder.add(x1, x2);
+    let constraint = Selectors {
+        qM: Fr::ONE,
+        qC: -Fr::from(12),
+        ..Default::default()
+    };
+    builder.gate(constraint.clone(), Some(x1), Some(x2));
+    let out = builder.sub(x1, x2);
+    builder.output(out);
+    let circuit = builder.build().unwrap();
+
+    let optimized = remove_dead_gates(&circuit).unwrap();
+
+    assert_eq!(optimized.number_gates(), 2);
+    assert_eq!(optimized.gates[0].selectors, constraint);
+    assert_eq!(optimized.outputs, vec![Wire::Gate(1)]);
+}
+
+#[test]
+fn test_merge_linear_gates() {
+    // a = x1 + x2, b = 2a - x1 = x1 + 2x2 merged into one gate, b * x3 keeps b as it reads three wires
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let x3 = builder.public_input();
+    let a = builder.add(x1, x2);
+    let b = builder.linear_combination(a, Fr::from(2), x1, -Fr::ONE, Fr::ZERO);
+    let out = builder.mul(b, x3);
+    builder.output(out);
+    let circuit = builder.build().unwrap();
+
+    let optimized = merge_linear_gates(&circuit).unwrap();
+
+    assert_eq!(
+        optimized.gates,
+        vec![
+            Gate::linear_combination(x1, Fr::ONE, x2, Fr::from(2), Fr::ZERO),
+            Gate::multiplication(Wire::Gate(0), x3),
+        ]
+    );
+    assert_eq!(
+        outputs(&optimized, &[3, 4, 5], &[]),
+        outputs(&circuit, &[3, 4, 5], &[])
+    );
+}
+
+#[test]
+fn test_optimize() {
+    // ((x + 1) + (x + 1)) * w + 0 * w, with a gate computing x * x that is never read
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let w = builder.witness();
+    let one = builder.constant(Fr::ONE);
+    let zero = builder.constant(Fr::ZERO);
+    let a = builder.add(x, one);
+    let b = builder.add(x, one);
+    let c = builder.add(a, b);
+    let d = builder.mul(c, w);
+    let e = builder.mul(zero, w);
+    builder.mul(x, x);
+    let out = builder.add(d, e);
+    builder.output(out);
+    let circuit = builder.build().unwrap();
+
+    let (optimized, reports) = optimize(&circuit).unwrap();
+
+    // 2x + 2 is computed by a single gate, then multiplied by w
+    assert_eq!(optimized.number_gates(), 2);
+    assert_eq!(
+        reports[0],
+        PassReport {
+            pass: "constant folding",
+            gates_before: 9,
+            gates_after: 5,
+        }
+    );
+    assert_eq!(reports.last().unwrap().gates_after, 2);
+    assert_eq!(reports[0].to_string(), "constant folding: 9 -> 5 gates");
+
+    let setup = setup_global_params::run(&optimized);
+    assert!(setup.d < circuit.d());
+    let proving_key = setup_proving_key::run(&setup, &optimized);
+    let verification_key = setup_verification_key::run(&setup, &optimized);
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &optimized,
+        &[Fr::from(4)],
+        &[Fr::from(3)],
+    )
+    .unwrap();
+
+    assert_eq!(proof.outputs, outputs(&circuit, &[4], &[3]));
+    assert_eq!(proof.outputs, vec![Fr::from(30)]);
+    assert!(verifier::run(&setup, &verification_key, &proof));
+}
+
+#[test]
+fn test_optimize_hints() {
+    // x / (x + 1) and x / (2 + 3): the inputs of the hints are merged or folded by the passes
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let a = builder.add_constant(x, Fr::ONE);
+    let quotient = builder.div(x, a);
+    let two = builder.constant(Fr::from(2));
+    let three = builder.constant(Fr::from(3));
+    let five = builder.add(two, three);
+    let fifth = builder.div(x, five);
+    builder.output(quotient);
+    builder.output(fifth);
+    let circuit = builder.build().unwrap();
+
+    let (optimized, _) = optimize(&circuit).unwrap();
+    assert_eq!(optimized.hints.len(), 2);
+    assert!(optimized.number_gates() < circuit.number_gates());
+    assert_eq!(outputs(&optimized, &[4], &[]), outputs(&circuit, &[4], &[]));
+    assert_eq!(
+        outputs(&optimized, &[4], &[]),
+        vec![Fr::from(4) / Fr::from(5), Fr::from(4) / Fr::from(5)]
+    );
+}
@@ -232,3 +232,73 @@ fn test_compose_polys() {
         f_g.coeffs, f_g_expected.coeffs
     );
 }
+
+#[test]
+fn test_geometric_interpolation_and_evaluation() {
+    let rng = &mut test_rng();
+    // A subgroup of order 12 and the first powers of an element of larger order
+    let Omega = plonk::common::utils::construct_Omega(12);
+    for (q, n) in [(Omega[1], 12), (Omega[1] * Omega[1], 6), (Fr::from(3), 37)] {
+        let poly = DensePolynomial::<Fr>::rand(n - 1, rng);
+        let x_vals: Vec<Fr> = (0..n).map(|i| q.pow([i as u64])).collect();
+        let y_vals: Vec<Fr> = x_vals.iter().map(|x| poly.evaluate(x)).collect();
+
+        assert_eq!(evaluate_polynomial_geometric(&poly, q, n), y_vals);
+        assert_eq!(interpolate_polynomial_geometric(q, &y_vals), poly);
+        // interpolate_polynomial takes the same path on these points, and the Lagrange interpolation
+        // once the points no longer start at 1
+        assert_eq!(interpolate_polynomial(&x_vals, &y_vals), poly);
+        let (mut x_rotated, mut y_rotated) = (x_vals.clone(), y_vals.clone());
+        x_rotated.rotate_left(1);
+        y_rotated.rotate_left(1);
+        assert_eq!(interpolate_polynomial(&x_rotated, &y_rotated), poly);
+    }
+
+    // Evaluations on more points than the degree, and of the zero polynomial
+    let poly = DensePolynomial::<Fr>::rand(4, rng);
+    let expected: Vec<Fr> = (0..20u64)
+        .map(|i| poly.evaluate(&Fr::from(5).pow([i])))
+        .collect();
+    assert_eq!(
+        evaluate_polynomial_geometric(&poly, Fr::from(5), 20),
+        expected
+    );
+    assert_eq!(
+        evaluate_polynomial_geometric(
+            &DensePolynomial::from_coefficients_vec(vec![]),
+            Fr::from(5),
+            3
+        ),
+        vec![Fr::ZERO; 3]
+    );
+}
+
+#[test]
+fn test_divide_polynomials() {
+    let rng = &mut test_rng();
+    for (f_degree, g_degree) in [(0, 0), (10, 3), (100, 1), (100, 99), (257, 64), (3, 10)] {
+        let f = DensePolynomial::<Fr>::rand(f_degree, rng);
+        let g = DensePolynomial::<Fr>::rand(g_degree, rng);
+        let (q, _) =
+            DenseOrSparsePolynomial::divide_with_q_and_r(&(&f).into(), &(&g).into()).unwrap();
+        assert_eq!(divide_polynomials(&f, &g), q);
+    }
+}
+
+#[test]
+fn test_compose_polys_scaling() {
+    // Composing with c*x scales the coefficients, the generic path must agree
+    let rng = &mut test_rng();
+    let f = DensePolynomial::<Fr>::rand(20, rng);
+    let c = Fr::rand(rng);
+    let scaling = DensePolynomial::from_coefficients_vec(vec![Fr::ZERO, c]);
+    // a trailing zero coefficient keeps g out of the fast path
+    let generic = DensePolynomial {
+        coeffs: vec![Fr::ZERO, c, Fr::ZERO],
+    };
+
+    let f_c_x = compose_polynomials(&f, &scaling);
+    assert_eq!(f_c_x, compose_polynomials(&f, &generic));
+    let x = Fr::rand(rng);
+    assert_eq!(f_c_x.evaluate(&x), f.evaluate(&(c * x)));
+}
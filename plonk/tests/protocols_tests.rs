@@ -1,20 +1,20 @@
 use ark_bls12_381::Fr;
 use ark_ff::{AdditiveGroup, Field, UniformRand};
-use ark_poly::Polynomial;
+use ark_poly::{univariate::DenseOrSparsePolynomial, Polynomial};
 use ark_std::rand::seq::SliceRandom;
 use plonk::common::{
     kzg::{kzg_commit, kzg_setup},
     polynomials::{interpolate_polynomial, random_polynomial},
     protocols::{
-        compute_q_zero_test, compute_q_zero_test_from_roots, compute_t1_T_S_zero_test,
+        compute_q_zero_test, compute_q_zero_test_from_roots, compute_t1_T_Q_zero_test,
         compute_t_and_t1_prescribed_permutation_check, compute_t_and_t1_product_check,
         compute_t_and_t1_product_check_rational_functions, compute_t_and_t1_sum_check,
-        prove_T_S_zero_test, prove_equality, prove_prescribed_permutation_check,
+        prove_T_Q_zero_test, prove_equality, prove_prescribed_permutation_check,
         prove_product_check, prove_product_check_rational_functions, prove_sum_check,
-        prove_zero_test, verify_T_S_zero_test, verify_equality,
+        prove_zero_test, verify_T_Q_zero_test, verify_equality,
         verify_prescribed_permutation_check, verify_product_check,
         verify_product_check_rational_functions, verify_sum_check, verify_zero_on_roots_test,
-        verify_zero_test,
+        verify_zero_test, Selectors,
     },
     utils::{
         construct_Omega, construct_vanishing_polynomial, construct_vanishing_polynomial_from_roots,
@@ -112,6 +112,10 @@ fn test_zero_test_success() {
 
     // check that f is divisble by Z_Omega
     assert_eq!(&q * &Z_Omega, f, "f must be divisible by Z_Omega");
+    // the division by the sparse Z_Omega matches the division by the dense one
+    let (q_dense, _) =
+        DenseOrSparsePolynomial::divide_with_q_and_r(&(&f).into(), &(&Z_Omega).into()).unwrap();
+    assert_eq!(q, q_dense, "q must match the long division by Z_Omega");
 
     // Verifier generates randomly r
     let r = Fr::rand(&mut rng);
@@ -844,6 +848,15 @@ fn test_prescribed_permutation_check_success() {
     // Prover constructs the polynomials t and t1 based on polynomials W,f,g and subset Omega
     let (t, t1) = compute_t_and_t1_prescribed_permutation_check(&Omega, &f, &g, &W, r, s);
 
+    // t(w^i) is the running product of (r - s*W(w^j) - f(w^j)) / (r - s*w^j - g(w^j)) for j <= i,
+    // evaluated point by point
+    let mut product = Fr::ONE;
+    for omega in &Omega {
+        product *=
+            (r - s * W.evaluate(omega) - f.evaluate(omega)) / (r - s * omega - g.evaluate(omega));
+        assert_eq!(t.evaluate(omega), product, "t must be the running product");
+    }
+
     // Prover computes quotient polynomial of t1 by Z_Omega
     let q = compute_q_zero_test(k, &t1);
 
@@ -943,7 +956,7 @@ fn test_prescribed_permutation_check_fail() {
 }
 
 #[test]
-fn test_T_S_zero_test_success() {
+fn test_T_Q_zero_test_success() {
     let mut rng = ark_std::test_rng();
 
     // this degree is used for kzg setup. We need to commit to polynomial q of degree 21.
@@ -1013,25 +1026,24 @@ fn test_T_S_zero_test_success() {
     let mut Omega_gates = vec![];
     (0..number_gates).for_each(|l| Omega_gates.push(Omega[3 * l]));
 
-    let mut gates = vec![];
-
-    // S encodes gates: S(w^3*l) = gate#l
-    // S(w^0) = 1 -- addition gate
-    gates.push(Fr::ONE);
-    // S(w^3) = 1 -- addition gate
-    gates.push(Fr::ONE);
-    // T(w^6) = 0 -- multiplication gate
-    gates.push(Fr::ZERO);
-
-    // Interpolate the polynomial S
-    let S = interpolate_polynomial(&Omega_gates, &gates);
-    assert_eq!(
-        S.degree(),
-        number_gates - 1,
-        "S must be of degree (number_gates - 1)"
+    // Q encodes gates: qL(w^3*l), qR(w^3*l), qO(w^3*l), qM(w^3*l), qC(w^3*l) are the selectors of gate#l
+    // gates 0 and 1 are additions: a + b - c = 0, gate 2 is a multiplication: a*b - c = 0
+    let gates = Selectors {
+        qL: vec![Fr::ONE, Fr::ONE, Fr::ZERO],
+        qR: vec![Fr::ONE, Fr::ONE, Fr::ZERO],
+        qO: vec![-Fr::ONE, -Fr::ONE, -Fr::ONE],
+        qM: vec![Fr::ZERO, Fr::ZERO, Fr::ONE],
+        qC: vec![Fr::ZERO, Fr::ZERO, Fr::ZERO],
+    };
+
+    // Interpolate the selector polynomials
+    let Q = gates.map(|selector| interpolate_polynomial(&Omega_gates, selector));
+    assert!(
+        Q.as_array().iter().all(|q| q.degree() < number_gates),
+        "Selector polynomials must be of degree at most (number_gates - 1)"
     );
 
-    let t1 = compute_t1_T_S_zero_test(Omega[1], &T, &S);
+    let t1 = compute_t1_T_Q_zero_test(Omega[1], &T, &Q);
 
     // check that t1 is of degree 24
     assert_eq!(t1.degree(), 24, "t1 must be of degree 24");
@@ -1047,9 +1059,9 @@ fn test_T_S_zero_test_success() {
     // construct Z_Omega_gates (vanishing polynomial) of subset Omega_gates
     let Z_Omega_gates = construct_vanishing_polynomial_from_roots(&Omega_gates);
 
-    // Prover computes commitments of T,S
+    // Prover computes commitments of T,Q
     let com_T = kzg_commit(&gp, &T).unwrap();
-    let com_S = kzg_commit(&gp, &S).unwrap();
+    let com_Q = Q.map(|q| kzg_commit(&gp, q).unwrap());
 
     // Prover computes quotient polynomial of t1 by Z_Omega
     let q = compute_q_zero_test_from_roots(&Omega_gates, &t1);
@@ -1067,18 +1079,18 @@ fn test_T_S_zero_test_success() {
     // Verifier generates randomly r
     let r = Fr::rand(&mut rng);
 
-    // Prover proves T_S zero test
-    let proof = prove_T_S_zero_test(&gp, Omega[1], &q, &T, &S, r);
+    // Prover proves T_Q zero test
+    let proof = prove_T_Q_zero_test(&gp, Omega[1], &q, &T, &Q, r);
 
-    // Verifier verifies T_S zero test
+    // Verifier verifies T_Q zero test
     assert!(
-        verify_T_S_zero_test(&gp, Omega[1], &Omega_gates, com_T, com_S, r, &proof),
-        "Verify must return true because T and S satisfy T_S zero test on Omega_gates"
+        verify_T_Q_zero_test(&gp, Omega[1], &Omega_gates, com_T, &com_Q, r, &proof),
+        "Verify must return true because T and Q satisfy T_Q zero test on Omega_gates"
     );
 }
 
 #[test]
-fn test_T_S_zero_test_fail() {
+fn test_T_Q_zero_test_fail() {
     let mut rng = ark_std::test_rng();
 
     // this degree is used for kzg setup. We need to commit to polynomial q of degree 21.
@@ -1093,15 +1105,15 @@ fn test_T_S_zero_test_fail() {
     let Omega = construct_Omega(d);
     assert_eq!(Omega.len(), d, "Omega must be of length d");
 
-    // generate T,S randomly
+    // generate T,Q randomly
     let T = random_polynomial(&mut rng, 11);
-    let S = random_polynomial(&mut rng, 2);
+    let Q = Selectors::default().map(|_: &()| random_polynomial(&mut rng, 2));
 
     // Define Omega_gates
     let mut Omega_gates = vec![];
     (0..number_gates).for_each(|l| Omega_gates.push(Omega[3 * l]));
 
-    let t1 = compute_t1_T_S_zero_test(Omega[1], &T, &S);
+    let t1 = compute_t1_T_Q_zero_test(Omega[1], &T, &Q);
 
     // check that t1 is of degree 24
     assert_eq!(t1.degree(), 24, "t1 must be of degree 24");
@@ -1117,9 +1129,9 @@ fn test_T_S_zero_test_fail() {
     // construct Z_Omega_gates (vanishing polynomial) of subset Omega_gates
     let Z_Omega_gates = construct_vanishing_polynomial_from_roots(&Omega_gates);
 
-    // Prover computes commitments of T,S
+    // Prover computes commitments of T,Q
     let com_T = kzg_commit(&gp, &T).unwrap();
-    let com_S = kzg_commit(&gp, &S).unwrap();
+    let com_Q = Q.map(|q| kzg_commit(&gp, q).unwrap());
 
     // Prover computes quotient polynomial of t1 by Z_Omega
     let q = compute_q_zero_test_from_roots(&Omega_gates, &t1);
@@ -1137,13 +1149,13 @@ fn test_T_S_zero_test_fail() {
     // Verifier generates randomly r
     let r = Fr::rand(&mut rng);
 
-    // Prover proves T_S zero test
-    let proof = prove_T_S_zero_test(&gp, Omega[1], &q, &T, &S, r);
+    // Prover proves T_Q zero test
+    let proof = prove_T_Q_zero_test(&gp, Omega[1], &q, &T, &Q, r);
 
-    // Verifier verifies T_S zero test
+    // Verifier verifies T_Q zero test
     assert_eq!(
-        verify_T_S_zero_test(&gp, Omega[1], &Omega_gates, com_T, com_S, r, &proof),
+        verify_T_Q_zero_test(&gp, Omega[1], &Omega_gates, com_T, &com_Q, r, &proof),
         false,
-        "Verify must return false because T and S do not satisfy T_S zero test on Omega_gates"
+        "Verify must return false because T and Q do not satisfy T_Q zero test on Omega_gates"
     );
 }
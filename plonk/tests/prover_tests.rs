@@ -0,0 +1,224 @@
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+use plonk::{
+    circuit::{builder::CircuitBuilder, CircuitError},
+    common::protocols::Selectors,
+    prover::{self, json::ProverInputsJson},
+    setup_global_params, setup_proving_key, setup_verification_key, verifier,
+};
+
+#[test]
+fn test_prover_inputs_from_json() {
+    let inputs_json: ProverInputsJson =
+        serde_json::from_str(r#"{ "pub_inputs": ["5", "0x6"], "witnesses": ["1"] }"#).unwrap();
+    let inputs = inputs_json.into_prover_inputs().unwrap();
+
+    assert_eq!(inputs.pub_inputs, vec![Fr::from(5), Fr::from(6)]);
+    assert_eq!(inputs.witnesses, vec![Fr::from(1)]);
+}
+
+#[test]
+fn test_prover_inputs_from_json_invalid_field_element() {
+    let inputs_json: ProverInputsJson =
+        serde_json::from_str(r#"{ "pub_inputs": ["5", "six"], "witnesses": [] }"#).unwrap();
+
+    assert!(matches!(
+        inputs_json.into_prover_inputs(),
+        Err(CircuitError::InvalidFieldElement(s)) if s == "six"
+    ));
+}
+
+#[test]
+fn test_prover_computes_output_for_any_inputs() {
+    // (x1 + x2) * (x2 + 1)
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let one = builder.witness();
+    let a = builder.add(x1, x2);
+    let b = builder.add(x2, one);
+    let out = builder.mul(a, b);
+    builder.output(out);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    // The same keys are used to prove two different statements
+    for (x1, x2, output) in [(5, 6, 77), (2, 3, 20)] {
+        let proof = prover::run(
+            &setup,
+            &proving_key,
+            &verification_key,
+            &circuit,
+            &[Fr::from(x1), Fr::from(x2)],
+            &[Fr::from(1)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            proof.outputs,
+            vec![Fr::from(output)],
+            "Output must be computed from the trace"
+        );
+        assert!(verifier::run(&setup, &verification_key, &proof));
+    }
+}
+
+#[test]
+fn test_prover_custom_gates() {
+    // 2(x1 - x2) + 3(x1 - x2) + 7
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let a = builder.sub(x1, x2);
+    let b = builder.mul_constant(a, Fr::from(3));
+    let out = builder.linear_combination(a, Fr::from(2), b, Fr::ONE, Fr::from(7));
+    builder.output(out);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[Fr::from(9), Fr::from(4)],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        proof.outputs,
+        vec![Fr::from(32)],
+        "Output must be 5(x1 - x2) + 7"
+    );
+    assert!(verifier::run(&setup, &verification_key, &proof));
+}
+
+#[test]
+fn test_prover_multiple_outputs() {
+    // Outputs x1 * x2, x1 and x1 + x2, opened at a gate output, a public input and the last gate
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let product = builder.mul(x1, x2);
+    let sum = builder.add(x1, x2);
+    builder.mul(product, sum);
+    builder.output(product);
+    builder.output(x1);
+    builder.output(sum);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+    assert_eq!(verification_key.output_indices, vec![2, 10, 5]);
+
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[Fr::from(3), Fr::from(4)],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        proof.outputs,
+        vec![Fr::from(12), Fr::from(3), Fr::from(7)],
+        "Outputs must be listed in the order they were declared"
+    );
+    assert!(verifier::run(&setup, &verification_key, &proof));
+}
+
+#[test]
+fn test_prover_no_output() {
+    // x1 * x2 = 12 is checked by a gate with qO = 0, nothing is output
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    builder.gate(
+        Selectors {
+            qM: Fr::ONE,
+            qC: -Fr::from(12),
+            ..Default::default()
+        },
+        Some(x1),
+        Some(x2),
+    );
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[Fr::from(3), Fr::from(4)],
+        &[],
+    )
+    .unwrap();
+
+    assert!(proof.outputs.is_empty());
+    assert!(verifier::run(&setup, &verification_key, &proof));
+}
+
+#[test]
+fn test_prover_any_number_of_public_inputs() {
+    // w * w + x1 + ... + xn, without public input the statement only depends on the witness
+    for number_public_inputs in [0, 1, 4] {
+        let mut builder = CircuitBuilder::new();
+        let w = builder.witness();
+        let mut sum = builder.mul(w, w);
+        for _ in 0..number_public_inputs {
+            let x = builder.public_input();
+            sum = builder.add(sum, x);
+        }
+        builder.output(sum);
+        let circuit = builder.build().unwrap();
+
+        let setup = setup_global_params::run(&circuit);
+        assert_eq!(setup.number_public_inputs, number_public_inputs);
+        let proving_key = setup_proving_key::run(&setup, &circuit);
+        let verification_key = setup_verification_key::run(&setup, &circuit);
+
+        let pub_inputs: Vec<Fr> = (1..=number_public_inputs as u64).map(Fr::from).collect();
+        let proof = prover::run(
+            &setup,
+            &proving_key,
+            &verification_key,
+            &circuit,
+            &pub_inputs,
+            &[Fr::from(3)],
+        )
+        .unwrap();
+
+        let expected = 9 + (1..=number_public_inputs as u64).sum::<u64>();
+        assert_eq!(proof.outputs, vec![Fr::from(expected)]);
+        assert!(verifier::run(&setup, &verification_key, &proof));
+
+        if number_public_inputs > 0 {
+            let mut wrong_inputs = pub_inputs.clone();
+            wrong_inputs[number_public_inputs - 1] += Fr::ONE;
+            assert!(
+                !verifier::verify(
+                    &setup,
+                    &verification_key,
+                    &proof,
+                    &wrong_inputs,
+                    &proof.outputs
+                ),
+                "Verify must return false because the last public input differs"
+            );
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+use ark_relations::{
+    lc,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable},
+};
+use plonk::{
+    circuit::{relations, CircuitError},
+    prover, setup_global_params, setup_proving_key, setup_verification_key, verifier,
+};
+
+// Proves knowledge of y such that z = x * y + x + 1, with x and z public
+struct Example {
+    x: Option<Fr>,
+    y: Option<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for Example {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let x_value = self.x.ok_or(SynthesisError::AssignmentMissing);
+        let y_value = self.y.ok_or(SynthesisError::AssignmentMissing);
+        let x = cs.new_input_variable(|| x_value)?;
+        let y = cs.new_witness_variable(|| y_value)?;
+        let z = cs.new_input_variable(|| Ok(x_value? * y_value? + x_value? + Fr::ONE))?;
+
+        cs.enforce_constraint(
+            lc!() + x,
+            lc!() + y,
+            lc!() + z - x - (Fr::ONE, Variable::One),
+        )
+    }
+}
+
+#[test]
+fn test_relations_synthesize() {
+    let (r1cs, assignment) = relations::synthesize(Example {
+        x: Some(Fr::from(3)),
+        y: Some(Fr::from(4)),
+    })
+    .unwrap();
+
+    assert_eq!(r1cs.number_wires, 4);
+    assert_eq!(r1cs.number_public_inputs, 2);
+    assert_eq!(r1cs.constraints.len(), 1);
+    assert_eq!(assignment, [1, 3, 16, 4].map(Fr::from));
+
+    let inputs = r1cs.prover_inputs(&assignment).unwrap();
+    assert_eq!(inputs.pub_inputs, vec![Fr::from(3), Fr::from(16)]);
+    assert_eq!(inputs.witnesses, vec![Fr::from(4)]);
+}
+
+#[test]
+fn test_relations_synthesize_missing_assignment() {
+    assert!(matches!(
+        relations::synthesize(Example {
+            x: Some(Fr::from(3)),
+            y: None,
+        }),
+        Err(CircuitError::Synthesis(SynthesisError::AssignmentMissing))
+    ));
+}
+
+#[test]
+fn test_relations_prove() {
+    let (r1cs, assignment) = relations::synthesize(Example {
+        x: Some(Fr::from(3)),
+        y: Some(Fr::from(4)),
+    })
+    .unwrap();
+    let circuit = r1cs.into_circuit().unwrap();
+    let inputs = r1cs.prover_inputs(&assignment).unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &inputs.pub_inputs,
+        &inputs.witnesses,
+    )
+    .unwrap();
+
+    assert!(verifier::run(&setup, &verification_key, &proof));
+    assert!(
+        !verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &[Fr::from(3), Fr::from(17)],
+            &[]
+        ),
+        "Verify must return false because z differs"
+    );
+}
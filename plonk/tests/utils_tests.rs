@@ -1,12 +1,14 @@
 use ark_bls12_381::{Fr, G1Projective as G1};
 use ark_ec::PrimeGroup;
-use ark_ff::{AdditiveGroup, Field, PrimeField};
-use ark_poly::univariate::DensePolynomial;
+use ark_ff::{AdditiveGroup, Field, PrimeField, UniformRand};
+use ark_poly::{univariate::DensePolynomial, Polynomial};
 use ark_serialize::CanonicalSerialize;
 use digest::Digest;
+use num_bigint::BigUint;
 use plonk::common::utils::{
     construct_Omega, construct_vanishing_polynomial, construct_vanishing_polynomial_from_roots,
     derive_challenge_from_commitments, derive_multiple_challenges_from_commitments,
+    evaluate_vanishing_polynomial_from_roots, parse_field_element, subgroup_size_at_least,
 };
 
 #[test]
@@ -21,6 +23,53 @@ fn test_construct_Omega() {
     }
 }
 
+#[test]
+fn test_subgroup_size_at_least() {
+    // 12 = 4 * 3 and 16 divide r - 1, while 13, 14 and 15 do not
+    assert_eq!(subgroup_size_at_least(12), 12);
+    assert_eq!(subgroup_size_at_least(13), 16);
+    assert_eq!(
+        subgroup_size_at_least(1),
+        2,
+        "Omega must have at least 2 elements"
+    );
+
+    let k = subgroup_size_at_least(1000);
+    assert!((1000..=1024).contains(&k));
+    assert_eq!(construct_Omega(k).len(), k);
+}
+
+#[test]
+fn test_parse_field_element() {
+    assert_eq!(parse_field_element("77"), Some(Fr::from(77)));
+    assert_eq!(parse_field_element("0x4d"), Some(Fr::from(77)));
+    assert_eq!(parse_field_element("0X4D"), Some(Fr::from(77)));
+    assert_eq!(parse_field_element("-1"), Some(-Fr::ONE));
+    assert_eq!(
+        parse_field_element(&(-Fr::ONE).to_string()),
+        Some(-Fr::ONE),
+        "r - 1 must be parsed as -1"
+    );
+
+    // r - 1 is the largest field element, r and above are rejected instead of being reduced
+    let r = BigUint::from(Fr::MODULUS);
+    let r_minus_1 = &r - 1u8;
+    assert_eq!(parse_field_element(&r_minus_1.to_string()), Some(-Fr::ONE));
+    assert_eq!(
+        parse_field_element(&format!("0x{:x}", r_minus_1)),
+        Some(-Fr::ONE)
+    );
+    assert_eq!(parse_field_element(&r.to_string()), None);
+    assert_eq!(parse_field_element(&format!("0x{:x}", r)), None);
+    assert_eq!(parse_field_element(&(&r + 1u8).to_string()), None);
+    assert_eq!(parse_field_element(&format!("-{}", r)), None);
+
+    assert_eq!(parse_field_element("0x"), None);
+    assert_eq!(parse_field_element("0xg1"), None);
+    assert_eq!(parse_field_element("seven"), None);
+    assert_eq!(parse_field_element(""), None);
+}
+
 #[test]
 fn test_construct_vanishing_polynomial() {
     let k = 4;
@@ -51,6 +100,34 @@ fn test_construct_vanishing_polynomial_from_roots() {
     );
 }
 
+#[test]
+fn test_vanishing_polynomial_from_roots_matches_product() {
+    // The product tree and the evaluation from the roots must match the product of the monomials
+    let mut rng = ark_std::test_rng();
+    for n in [0, 1, 7, 64] {
+        let roots: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+        let expected = roots.iter().fold(
+            DensePolynomial {
+                coeffs: vec![Fr::ONE],
+            },
+            |product, root| {
+                &product
+                    * &DensePolynomial {
+                        coeffs: vec![-*root, Fr::ONE],
+                    }
+            },
+        );
+        let Z = construct_vanishing_polynomial_from_roots(&roots);
+        assert_eq!(Z, expected);
+
+        let x = Fr::rand(&mut rng);
+        assert_eq!(
+            evaluate_vanishing_polynomial_from_roots(&roots, x),
+            Z.evaluate(&x)
+        );
+    }
+}
+
 #[test]
 fn test_derive_challenge_from_single_commitment() {
     // Use generator as deterministic commitment
@@ -0,0 +1,182 @@
+use ark_bls12_381::Fr;
+use plonk::{
+    circuit::builder::CircuitBuilder,
+    common::proof::Proof,
+    prover, setup_global_params,
+    setup_global_params::SetupGlobalParamsOutput,
+    setup_proving_key, setup_verification_key,
+    setup_verification_key::SetupVerificationKeyOutput,
+    verifier::{self, json::StatementJson},
+};
+
+// Prove x1 + x2 = 11 and (x1 + x2) * (x2 + 1) = 77 for x1 = 5, x2 = 6
+fn prove_example() -> (SetupGlobalParamsOutput, SetupVerificationKeyOutput, Proof) {
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let one = builder.witness();
+    let a = builder.add(x1, x2);
+    let b = builder.add(x2, one);
+    let out = builder.mul(a, b);
+    builder.output(a);
+    builder.output(out);
+    let circuit = builder.build().unwrap();
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[Fr::from(5), Fr::from(6)],
+        &[Fr::from(1)],
+    )
+    .unwrap();
+
+    (setup, verification_key, proof)
+}
+
+#[test]
+fn test_verify_expected_statement() {
+    let (setup, verification_key, proof) = prove_example();
+    let pub_inputs = [Fr::from(5), Fr::from(6)];
+    let outputs = [Fr::from(11), Fr::from(77)];
+
+    assert!(
+        verifier::verify(&setup, &verification_key, &proof, &pub_inputs, &outputs),
+        "Verify must return true for the statement the proof is bound to"
+    );
+    assert!(verifier::run(&setup, &verification_key, &proof));
+
+    // Wrong public input
+    assert!(
+        !verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &[Fr::from(5), Fr::from(7)],
+            &outputs
+        ),
+        "Verify must return false because public inputs differ"
+    );
+    // Wrong output
+    assert!(
+        !verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &pub_inputs,
+            &[Fr::from(11), Fr::from(78)]
+        ),
+        "Verify must return false because outputs differ"
+    );
+    // Outputs in the wrong order
+    assert!(
+        !verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &pub_inputs,
+            &[Fr::from(77), Fr::from(11)]
+        ),
+        "Verify must return false because outputs are swapped"
+    );
+    // Wrong number of outputs
+    assert!(
+        !verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &pub_inputs,
+            &[Fr::from(77)]
+        ),
+        "Verify must return false because an output is missing"
+    );
+    // Wrong number of public inputs
+    assert!(
+        !verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &pub_inputs[..1],
+            &outputs
+        ),
+        "Verify must return false because a public input is missing"
+    );
+}
+
+#[test]
+fn test_verify_ignores_statement_carried_by_proof() {
+    let (setup, verification_key, mut proof) = prove_example();
+
+    // The prover claims another statement than the one it proved
+    proof.pub_inputs = vec![Fr::from(2), Fr::from(3)];
+    proof.outputs = vec![Fr::from(5), Fr::from(20)];
+
+    assert!(
+        !verifier::run(&setup, &verification_key, &proof),
+        "Verify must return false because the claimed statement was not proved"
+    );
+    assert!(
+        verifier::verify(
+            &setup,
+            &verification_key,
+            &proof,
+            &[Fr::from(5), Fr::from(6)],
+            &[Fr::from(11), Fr::from(77)]
+        ),
+        "Verify must only depend on the statement given by the caller"
+    );
+}
+
+#[test]
+fn test_statement_from_json() {
+    let statement_json: StatementJson =
+        serde_json::from_str(r#"{ "pub_inputs": ["5", "0x6"], "outputs": ["11", "77"] }"#).unwrap();
+    let statement = statement_json.into_statement().unwrap();
+
+    assert_eq!(statement.pub_inputs, vec![Fr::from(5), Fr::from(6)]);
+    assert_eq!(statement.outputs, vec![Fr::from(11), Fr::from(77)]);
+}
+
+#[test]
+fn test_verify_constant_bound_by_verification_key() {
+    // (x1 + x2) * (x2 + k) with the constant k fixed by the circuit
+    let circuit_with_constant = |k: u64| {
+        let mut builder = CircuitBuilder::new();
+        let x1 = builder.public_input();
+        let x2 = builder.public_input();
+        let k = builder.constant(Fr::from(k));
+        let a = builder.add(x1, x2);
+        let b = builder.add(x2, k);
+        let out = builder.mul(a, b);
+        builder.output(out);
+        builder.build().unwrap()
+    };
+    let circuit = circuit_with_constant(1);
+    let other_circuit = circuit_with_constant(2);
+
+    let setup = setup_global_params::run(&circuit);
+    let proving_key = setup_proving_key::run(&setup, &circuit);
+    let verification_key = setup_verification_key::run(&setup, &circuit);
+    let proof = prover::run(
+        &setup,
+        &proving_key,
+        &verification_key,
+        &circuit,
+        &[Fr::from(5), Fr::from(6)],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(proof.outputs, vec![Fr::from(77)]);
+    assert!(verifier::run(&setup, &verification_key, &proof));
+
+    // The verification key of the circuit using k = 2 must reject the proof computed with k = 1
+    let other_verification_key = setup_verification_key::run(&setup, &other_circuit);
+    assert!(
+        !verifier::run(&setup, &other_verification_key, &proof),
+        "Verify must return false because the constant is fixed by the verification key"
+    );
+}
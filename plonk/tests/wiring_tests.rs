@@ -0,0 +1,85 @@
+use ark_poly::Polynomial;
+use plonk::{
+    circuit::{builder::CircuitBuilder, wiring::CopyConstraints},
+    common::utils::construct_Omega,
+    setup_proving_key::compute_proving_key,
+};
+
+#[test]
+fn test_copy_constraints_cycles() {
+    let mut copy_constraints = CopyConstraints::new(8);
+    copy_constraints.connect(5, 1);
+    copy_constraints.connect(3, 6);
+    copy_constraints.connect(1, 7);
+    copy_constraints.connect(7, 5); // already connected
+
+    assert!(copy_constraints.are_connected(5, 7));
+    assert!(!copy_constraints.are_connected(5, 6));
+    assert_eq!(
+        copy_constraints.cycles(),
+        vec![vec![0], vec![1, 5, 7], vec![2], vec![3, 6], vec![4]],
+        "Cycles must be the classes of connected slots"
+    );
+    assert_eq!(
+        copy_constraints.permutation(),
+        vec![0, 5, 2, 6, 4, 7, 3, 1],
+        "Permutation must rotate every cycle"
+    );
+}
+
+#[test]
+fn test_copy_constraints_wiring_polynomial() {
+    let d = 8;
+    let Omega = construct_Omega(d);
+
+    let mut copy_constraints = CopyConstraints::new(d);
+    copy_constraints.connect(0, 4);
+    copy_constraints.connect(4, 2);
+
+    let W = copy_constraints.wiring_polynomial(&Omega);
+    let sigma = copy_constraints.permutation();
+    for i in 0..d {
+        assert_eq!(
+            W.evaluate(&Omega[i]),
+            Omega[sigma[i]],
+            "W(w^i) must be w^sigma(i)"
+        );
+    }
+    // W(w^0, w^2, w^4) = (w^2, w^4, w^0)
+    assert_eq!(W.evaluate(&Omega[0]), Omega[2]);
+    assert_eq!(W.evaluate(&Omega[4]), Omega[0]);
+    // W(w^1) = w^1
+    assert_eq!(W.evaluate(&Omega[1]), Omega[1]);
+}
+
+#[test]
+fn test_copy_constraints_of_circuit() {
+    // x^2 + x
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let x2 = builder.mul(x, x);
+    builder.add(x2, x);
+    let circuit = builder.build().unwrap();
+    let d = circuit.d();
+
+    let copy_constraints = circuit.copy_constraints(d);
+    let x_slot = circuit.wire_index(x, d);
+    for slot in [0, 1, 4] {
+        assert!(
+            copy_constraints.are_connected(slot, x_slot),
+            "x must be wired to its uses"
+        );
+    }
+    assert!(
+        copy_constraints.are_connected(2, 3),
+        "x^2 must be wired to its use"
+    );
+    assert!(!copy_constraints.are_connected(2, 0));
+
+    // The proving key wiring polynomial is the one of the copy constraints
+    let proving_key = compute_proving_key(&circuit);
+    assert_eq!(
+        proving_key.W,
+        copy_constraints.wiring_polynomial(&construct_Omega(d))
+    );
+}
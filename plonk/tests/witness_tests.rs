@@ -0,0 +1,253 @@
+use ark_bls12_381::Fr;
+use ark_ff::{AdditiveGroup, Field};
+use plonk::circuit::{
+    builder::CircuitBuilder,
+    mock::check_trace,
+    witness::{generate_trace, topological_order},
+    Circuit, CircuitError, Gate, Hint, HintOp, Wire,
+};
+use plonk::common::protocols::Selectors;
+
+// Circuit computing (x1 + x2) * (x2 + 1), with the constant 1 given as witness
+fn example_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let one = builder.witness();
+    let a = builder.add(x1, x2);
+    let b = builder.add(x2, one);
+    builder.mul(a, b);
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_generate_trace() {
+    let circuit = example_circuit();
+    let d = circuit.d();
+
+    let trace = generate_trace(&circuit, &[Fr::from(5), Fr::from(6)], &[Fr::from(1)]).unwrap();
+
+    let mut expected = vec![5, 6, 11, 6, 1, 7, 11, 7, 77]
+        .into_iter()
+        .map(Fr::from)
+        .collect::<Vec<_>>();
+    expected.extend(vec![Fr::from(1), Fr::from(6), Fr::from(5)]); // w^-3, w^-2, w^-1
+    assert_eq!(trace.len(), d);
+    assert_eq!(
+        trace, expected,
+        "Trace must match the evaluation of the gates"
+    );
+}
+
+#[test]
+fn test_generate_trace_other_inputs() {
+    let circuit = example_circuit();
+
+    let trace = generate_trace(&circuit, &[Fr::from(2), Fr::from(3)], &[Fr::from(1)]).unwrap();
+
+    // (2 + 3) * (3 + 1) = 20
+    assert_eq!(trace[8], Fr::from(20), "Output of the last gate must be 20");
+}
+
+#[test]
+fn test_generate_trace_gates_out_of_order() {
+    // gate#0 = gate#1 * x, gate#1 = x + x
+    let circuit = Circuit {
+        number_public_inputs: 1,
+        number_witnesses: 0,
+        gates: vec![
+            Gate::multiplication(Wire::Gate(1), Wire::PublicInput(0)),
+            Gate::addition(Wire::PublicInput(0), Wire::PublicInput(0)),
+        ],
+        outputs: vec![],
+        hints: vec![],
+    };
+    assert_eq!(topological_order(&circuit).unwrap(), vec![1, 0]);
+
+    let trace = generate_trace(&circuit, &[Fr::from(3)], &[]).unwrap();
+    assert_eq!(trace[5], Fr::from(6), "x + x must be 6");
+    assert_eq!(
+        trace[0],
+        Fr::from(6),
+        "Input of gate#0 must be the output of gate#1"
+    );
+    assert_eq!(trace[2], Fr::from(18), "(x + x) * x must be 18");
+    assert_eq!(trace[6], Fr::ZERO, "Padding slot must be zero");
+}
+
+#[test]
+fn test_generate_trace_cyclic_gates() {
+    let circuit = Circuit {
+        number_public_inputs: 1,
+        number_witnesses: 0,
+        gates: vec![
+            Gate::addition(Wire::PublicInput(0), Wire::Gate(1)),
+            Gate::multiplication(Wire::Gate(0), Wire::PublicInput(0)),
+        ],
+        outputs: vec![],
+        hints: vec![],
+    };
+
+    assert!(matches!(
+        generate_trace(&circuit, &[Fr::from(3)], &[]),
+        Err(CircuitError::CyclicGates(_))
+    ));
+}
+
+#[test]
+fn test_generate_trace_wrong_number_of_inputs() {
+    let circuit = example_circuit();
+
+    assert!(matches!(
+        generate_trace(&circuit, &[Fr::from(5)], &[Fr::from(1)]),
+        Err(CircuitError::WrongNumberOfValues {
+            expected: 2,
+            got: 1,
+            ..
+        })
+    ));
+    assert!(matches!(
+        generate_trace(&circuit, &[Fr::from(5), Fr::from(6)], &[]),
+        Err(CircuitError::WrongNumberOfValues {
+            expected: 1,
+            got: 0,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_generate_trace_custom_gates() {
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let y = builder.public_input();
+    let a = builder.sub(x, y);
+    let b = builder.mul_constant(a, Fr::from(3));
+    let c = builder.linear_combination(a, Fr::from(2), b, -Fr::ONE, Fr::from(10));
+    // 2c = x*y
+    builder.gate(
+        Selectors {
+            qM: Fr::ONE,
+            qO: -Fr::from(2),
+            ..Default::default()
+        },
+        Some(x),
+        Some(y),
+    );
+    let circuit = builder.build().unwrap();
+    assert_eq!(c, Wire::Gate(2));
+
+    let trace = generate_trace(&circuit, &[Fr::from(9), Fr::from(4)], &[]).unwrap();
+
+    assert_eq!(trace[2], Fr::from(5), "x - y must be 5");
+    assert_eq!(trace[4], Fr::ZERO, "Unused input must be zero");
+    assert_eq!(trace[5], Fr::from(15), "3(x - y) must be 15");
+    assert_eq!(trace[8], Fr::from(5), "2a - b + 10 must be 5");
+    assert_eq!(trace[11], Fr::from(18), "x*y/2 must be 18");
+    for (l, gate) in circuit.gates.iter().enumerate() {
+        assert_eq!(
+            gate.selectors
+                .evaluate_gate(trace[3 * l], trace[3 * l + 1], trace[3 * l + 2]),
+            Fr::ZERO,
+            "Gate#{} must be satisfied by the trace",
+            l
+        );
+    }
+}
+
+#[test]
+fn test_generate_trace_constants() {
+    // (x1 + x2) * (x2 + 1), with the constant 1 fixed by the circuit
+    let mut builder = CircuitBuilder::new();
+    let x1 = builder.public_input();
+    let x2 = builder.public_input();
+    let one = builder.constant(Fr::ONE);
+    let a = builder.add(x1, x2);
+    let b = builder.add(x2, one);
+    builder.mul(a, b);
+    let circuit = builder.build().unwrap();
+
+    let trace = generate_trace(&circuit, &[Fr::from(5), Fr::from(6)], &[]).unwrap();
+
+    assert_eq!(trace[2], Fr::ONE, "Output of the constant gate must be 1");
+    assert_eq!(trace[7], Fr::ONE, "Constant must be copied to its uses");
+    assert_eq!(trace[11], Fr::from(77));
+}
+
+#[test]
+fn test_generate_trace_hints() {
+    // x / (x - y) and a square root of y, between two witnesses given by the prover
+    let mut builder = CircuitBuilder::new();
+    let x = builder.public_input();
+    let y = builder.public_input();
+    builder.witness();
+    let difference = builder.sub(x, y);
+    let quotient = builder.div(x, difference);
+    let root = builder.sqrt(y);
+    builder.witness();
+    let circuit = builder.build().unwrap();
+    assert_eq!(circuit.given_witnesses(), vec![0, 3]);
+
+    let (x, y) = (Fr::from(10), Fr::from(4));
+    let trace = generate_trace(&circuit, &[x, y], &[Fr::from(7), Fr::from(8)]).unwrap();
+    let d = circuit.d();
+    assert_eq!(trace[circuit.wire_index(quotient, d)] * (x - y), x);
+    assert_eq!(trace[circuit.wire_index(root, d)].square(), y);
+    assert_eq!(trace[circuit.wire_index(Wire::Witness(3), d)], Fr::from(8));
+    assert!(check_trace(&circuit, &trace).is_empty());
+
+    // Hints are computed whatever their values, the gates checking them are not satisfied
+    let trace = generate_trace(&circuit, &[x, x], &[Fr::from(7), Fr::from(8)]).unwrap();
+    assert_eq!(trace[circuit.wire_index(Wire::Witness(1), d)], Fr::ZERO);
+    assert!(!check_trace(&circuit, &trace).is_empty());
+}
+
+#[test]
+fn test_generate_trace_hints_out_of_order() {
+    // gate#0 = w * x with w = 1 / gate#1, gate#1 = x + x
+    let circuit = Circuit {
+        number_public_inputs: 1,
+        number_witnesses: 1,
+        gates: vec![
+            Gate::multiplication(Wire::Witness(0), Wire::PublicInput(0)),
+            Gate::addition(Wire::PublicInput(0), Wire::PublicInput(0)),
+        ],
+        outputs: vec![],
+        hints: vec![Hint {
+            witness: 0,
+            op: HintOp::Inverse,
+            input: Wire::Gate(1),
+        }],
+    };
+
+    let trace = generate_trace(&circuit, &[Fr::from(3)], &[]).unwrap();
+    assert_eq!(
+        trace[2],
+        Fr::from(2).inverse().unwrap(),
+        "x / (x + x) is 1/2"
+    );
+
+    // gate#0 depends on its own output through w = 1 / gate#0, and w = 1 / w on itself
+    let mut circuit = circuit;
+    circuit.hints[0].input = Wire::Gate(0);
+    assert!(matches!(
+        generate_trace(&circuit, &[Fr::from(3)], &[]),
+        Err(CircuitError::CyclicGates(0))
+    ));
+    circuit.hints[0].input = Wire::Witness(0);
+    assert!(matches!(
+        generate_trace(&circuit, &[Fr::from(3)], &[]),
+        Err(CircuitError::CyclicHint(0))
+    ));
+}
+
+#[test]
+fn test_hint_ops() {
+    let x = Fr::from(9);
+    assert_eq!(HintOp::Inverse.evaluate(x) * x, Fr::ONE);
+    assert_eq!(HintOp::Inverse.evaluate(Fr::ZERO), Fr::ZERO);
+    assert_eq!(HintOp::SquareRoot.evaluate(x).square(), x);
+    assert_eq!(HintOp::Bit(0).evaluate(x), Fr::ONE);
+    assert_eq!(HintOp::Bit(2).evaluate(x), Fr::ZERO);
+    assert_eq!(HintOp::Bit(3).evaluate(x), Fr::ONE);
+}